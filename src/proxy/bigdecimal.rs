@@ -0,0 +1,89 @@
+use std::str::FromStr;
+
+use bigdecimal::{BigDecimal, ToPrimitive};
+use rust_decimal::Decimal;
+use surrealdb::sql::{Number, Value};
+
+use crate::proxy::default::{SurrealDeserializer, SurrealSerializer, SurrealSerializerRef};
+use crate::surreal_qr::SurrealResponseError;
+
+/// Convert a float through its shortest decimal representation, failing if the decimal does not
+/// convert back to the same float.
+fn big_decimal_from_float<T>(
+    float: f64,
+    value: &Value,
+) -> Result<BigDecimal, SurrealResponseError> {
+    if !float.is_finite() {
+        return Err(SurrealResponseError::ExpectedANumberDecimal.mismatch::<T>(value));
+    }
+
+    match BigDecimal::from_str(&float.to_string()) {
+        Ok(decimal) if decimal.to_f64() == Some(float) => Ok(decimal),
+        _ => Err(SurrealResponseError::NumberPrecisionLoss.mismatch::<T>(value)),
+    }
+}
+
+/// Stored as a decimal when it fits in one, otherwise as a string so no digit is lost.
+impl SurrealSerializer for BigDecimal {
+    fn serialize(self) -> Value {
+        self.serialize_ref()
+    }
+}
+
+impl SurrealSerializerRef for BigDecimal {
+    fn serialize_ref(&self) -> Value {
+        let digits = self.to_string();
+        match Decimal::from_str_exact(&digits) {
+            Ok(decimal) => Value::Number(Number::Decimal(decimal)),
+            Err(_) => Value::from(digits),
+        }
+    }
+}
+
+/// Reads decimals, ints, floats that convert back exactly from their shortest representation and
+/// decimal strings.
+impl SurrealDeserializer for BigDecimal {
+    fn deserialize(value: &Value) -> Result<BigDecimal, SurrealResponseError> {
+        let digits = match value {
+            Value::Number(Number::Decimal(decimal)) => decimal.to_string(),
+            Value::Number(Number::Int(int)) => return Ok(BigDecimal::from(*int)),
+            Value::Number(Number::Float(float)) => {
+                return big_decimal_from_float::<Self>(*float, value)
+            }
+            Value::Strand(strand) => strand.as_str().to_owned(),
+            _ => return Err(SurrealResponseError::ExpectedANumberDecimal.mismatch::<Self>(value)),
+        };
+
+        BigDecimal::from_str(&digits)
+            .map_err(|_| SurrealResponseError::ExpectedANumberDecimal.mismatch::<Self>(value))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_big_decimal() {
+        let small = BigDecimal::from_str("12.34").unwrap();
+        assert!(matches!(
+            small.serialize_ref(),
+            Value::Number(Number::Decimal(_))
+        ));
+        assert_eq!(
+            BigDecimal::deserialize(&small.serialize_ref()),
+            Ok(small.clone())
+        );
+        assert_eq!(BigDecimal::deserialize(&Value::from(12.34)), Ok(small));
+        assert_eq!(
+            BigDecimal::deserialize(&Value::from(f64::NAN))
+                .unwrap_err()
+                .kind(),
+            &SurrealResponseError::ExpectedANumberDecimal
+        );
+
+        let huge = BigDecimal::from_str("123456789012345678901234567890.123456789").unwrap();
+        assert!(matches!(huge.serialize_ref(), Value::Strand(_)));
+        assert_eq!(BigDecimal::deserialize(&huge.serialize_ref()), Ok(huge));
+    }
+}
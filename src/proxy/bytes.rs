@@ -0,0 +1,40 @@
+use bytes::Bytes;
+use surrealdb::sql::Value;
+
+use crate::proxy::default::{
+    bytes_from_value, SurrealDeserializer, SurrealSerializer, SurrealSerializerRef,
+};
+use crate::surreal_qr::SurrealResponseError;
+
+impl SurrealSerializer for Bytes {
+    fn serialize(self) -> Value {
+        Value::Bytes(Vec::from(self).into())
+    }
+}
+
+impl SurrealSerializerRef for Bytes {
+    fn serialize_ref(&self) -> Value {
+        Value::Bytes(self.to_vec().into())
+    }
+}
+
+impl SurrealDeserializer for Bytes {
+    fn deserialize(value: &Value) -> Result<Bytes, SurrealResponseError> {
+        Ok(Bytes::from(bytes_from_value::<Self>(value)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let bytes = Bytes::from_static(b"surreal");
+        assert_eq!(
+            Bytes::deserialize(&bytes.serialize_ref()),
+            Ok(bytes.clone())
+        );
+        assert_eq!(Bytes::deserialize(&Value::from("c3VycmVhbA")), Ok(bytes));
+    }
+}
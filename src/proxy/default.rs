@@ -1,14 +1,62 @@
-use chrono::{DateTime, Utc};
+use base64::alphabet;
+use base64::engine::{DecodePaddingMode, GeneralPurpose, GeneralPurposeConfig};
+use base64::Engine;
+use chrono::{DateTime, FixedOffset, Local, NaiveDate, NaiveDateTime, NaiveTime, Utc};
+use std::borrow::Cow;
+use std::collections::{BTreeMap, BTreeSet, BinaryHeap, HashMap, HashSet, LinkedList, VecDeque};
+use std::fmt::Display;
+use std::hash::{BuildHasher, Hash};
+use std::net::{IpAddr, Ipv4Addr, Ipv6Addr, SocketAddr, SocketAddrV4, SocketAddrV6};
+use std::num::{
+    IntErrorKind, NonZeroI128, NonZeroI16, NonZeroI32, NonZeroI64, NonZeroI8, NonZeroIsize,
+    NonZeroU128, NonZeroU16, NonZeroU32, NonZeroU64, NonZeroU8, NonZeroUsize, ParseIntError,
+};
+use std::ops::{Deref, DerefMut};
+use std::path::{Path, PathBuf};
+use std::rc::Rc;
+use std::str::FromStr;
+use std::sync::Arc;
 use std::time::Duration;
-use surrealdb::sql::{Thing, Value};
+use surrealdb::sql::{Bytes, Number, Object, Thing, Value};
 use uuid::Uuid;
 
+use crate::proxy::enum_repr::{EnumRepr, VariantContent};
 use crate::surreal_qr::SurrealResponseError;
 
 pub trait SurrealSerializer {
     fn serialize(self) -> Value;
 }
 
+/// Serialize from a reference, so a value can be written without being cloned first.
+pub trait SurrealSerializerRef {
+    fn serialize_ref(&self) -> Value;
+}
+
+impl<T> SurrealSerializerRef for &T
+where
+    T: SurrealSerializerRef + ?Sized,
+{
+    fn serialize_ref(&self) -> Value {
+        (**self).serialize_ref()
+    }
+}
+
+/// Types that are cheap to copy serialize from a reference through their owned serializer.
+macro_rules! copy_serializer_ref_impls {
+    ($($ty:ty),*) => {
+        $(
+            impl SurrealSerializerRef for $ty {
+                fn serialize_ref(&self) -> Value {
+                    (*self).serialize()
+                }
+            }
+        )*
+    };
+}
+
+#[allow(unused_imports)]
+pub(crate) use copy_serializer_ref_impls;
+
 pub trait SurrealDeserializer
 where
     Self: Sized,
@@ -20,73 +68,267 @@ where
         }
     }
 
+    /// Deserialize the field `field` of `object`, recording the field name in the error path.
+    fn from_field(object: &Object, field: &str) -> Result<Self, SurrealResponseError> {
+        Self::from_option(object.get(field)).map_err(|e| e.at_field(field))
+    }
+
     fn deserialize(value: &Value) -> Result<Self, SurrealResponseError>;
 }
 
-impl SurrealSerializer for i32 {
-    fn serialize(self) -> Value {
-        Value::from(self)
-    }
-}
+/// Read `value` as an exact integer, rejecting fractions and numbers that do not fit in `T`.
+fn checked_integer<T>(value: &Value) -> Result<T, SurrealResponseError>
+where
+    T: TryFrom<i128>,
+{
+    let Value::Number(number) = value else {
+        return Err(SurrealResponseError::ExpectedANumberI64.mismatch::<T>(value));
+    };
 
-impl SurrealDeserializer for i32 {
-    fn deserialize(value: &Value) -> Result<i32, SurrealResponseError> {
-        if let Value::Number(n) = value {
-            Ok(n.as_int() as i32)
+    let integer: i128 = match number {
+        Number::Int(v) => *v as i128,
+        Number::Float(v) => {
+            if !v.is_finite() || *v < i128::MIN as f64 || *v >= i128::MAX as f64 {
+                return Err(SurrealResponseError::NumberOverflow.mismatch::<T>(value));
+            }
+            if v.fract() != 0.0 {
+                return Err(SurrealResponseError::NumberPrecisionLoss.mismatch::<T>(value));
+            }
+
+            *v as i128
+        }
+        Number::Decimal(v) => {
+            if !v.is_integer() {
+                return Err(SurrealResponseError::NumberPrecisionLoss.mismatch::<T>(value));
+            }
+
+            i128::try_from(*v)
+                .map_err(|_| SurrealResponseError::NumberOverflow.mismatch::<T>(value))?
+        }
+        _ => return Err(SurrealResponseError::ExpectedANumberI64.mismatch::<T>(value)),
+    };
+
+    T::try_from(integer).map_err(|_| {
+        if integer < 0 && T::try_from(-1).is_err() {
+            SurrealResponseError::NumberSignMismatch.mismatch::<T>(value)
         } else {
-            Err(SurrealResponseError::ExpectedANumberI64)
+            SurrealResponseError::NumberOverflow.mismatch::<T>(value)
         }
-    }
+    })
 }
 
-impl SurrealSerializer for u32 {
-    fn serialize(self) -> Value {
-        Value::from(self)
+/// Opt-in lossy integer deserialization: out of range numbers are clamped to the bounds of `T`
+/// and fractions are truncated toward zero, instead of being rejected.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct Saturating<T>(pub T);
+
+impl<T> Deref for Saturating<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
     }
 }
 
-impl SurrealDeserializer for u32 {
-    fn deserialize(value: &Value) -> Result<u32, SurrealResponseError> {
-        if let Value::Number(n) = value {
-            Ok(n.as_int() as u32)
-        } else {
-            Err(SurrealResponseError::ExpectedANumberI64)
-        }
+impl<T> SurrealSerializer for Saturating<T>
+where
+    T: SurrealSerializer,
+{
+    fn serialize(self) -> Value {
+        self.0.serialize()
     }
 }
 
-impl SurrealSerializer for u64 {
-    fn serialize(self) -> Value {
-        Value::from(self)
+impl<T> SurrealSerializerRef for Saturating<T>
+where
+    T: SurrealSerializerRef,
+{
+    fn serialize_ref(&self) -> Value {
+        self.0.serialize_ref()
     }
 }
 
-impl SurrealDeserializer for u64 {
-    fn deserialize(value: &Value) -> Result<u64, SurrealResponseError> {
-        if let Value::Number(n) = value {
-            Ok(n.as_int() as u64)
-        } else {
-            Err(SurrealResponseError::ExpectedANumberI64)
+/// Read a string holding an integer, the way 128-bit integers too wide for a number are stored.
+fn integer_from_strand<T>(value: &Value, strand: &str) -> Result<T, SurrealResponseError>
+where
+    T: FromStr<Err = ParseIntError>,
+{
+    strand.parse::<T>().map_err(|e| match e.kind() {
+        IntErrorKind::PosOverflow | IntErrorKind::NegOverflow => {
+            SurrealResponseError::NumberOverflow.mismatch::<T>(value)
         }
-    }
+        _ if strand.starts_with('-') && strand[1..].parse::<u128>().is_ok() => {
+            SurrealResponseError::NumberSignMismatch.mismatch::<T>(value)
+        }
+        _ => SurrealResponseError::ExpectedANumberI64.mismatch::<T>(value),
+    })
 }
 
-impl SurrealSerializer for i64 {
-    fn serialize(self) -> Value {
-        Value::from(self)
-    }
+macro_rules! integer_impls {
+    ($($int:ty),*) => {
+        $(
+            impl SurrealSerializer for $int {
+                fn serialize(self) -> Value {
+                    match i64::try_from(self) {
+                        Ok(v) => Value::from(v),
+                        Err(_) => Value::Number(Number::Decimal(self.into())),
+                    }
+                }
+            }
+
+            impl SurrealDeserializer for $int {
+                fn deserialize(value: &Value) -> Result<$int, SurrealResponseError> {
+                    checked_integer(value)
+                }
+            }
+        )*
+    };
 }
 
-impl SurrealDeserializer for i64 {
-    fn deserialize(value: &Value) -> Result<i64, SurrealResponseError> {
-        if let Value::Number(n) = value {
-            Ok(n.as_int())
-        } else {
-            Err(SurrealResponseError::ExpectedANumberI64)
-        }
-    }
+/// 128-bit integers outside of the `i64` range are stored as strings, since neither
+/// an int nor a decimal can hold every value of them.
+macro_rules! wide_integer_impls {
+    ($($int:ty),*) => {
+        $(
+            impl SurrealSerializer for $int {
+                fn serialize(self) -> Value {
+                    match i64::try_from(self) {
+                        Ok(v) => Value::from(v),
+                        Err(_) => Value::from(self.to_string()),
+                    }
+                }
+            }
+
+            impl SurrealDeserializer for $int {
+                fn deserialize(value: &Value) -> Result<$int, SurrealResponseError> {
+                    match value {
+                        Value::Strand(strand) => integer_from_strand(value, strand.as_str()),
+                        _ => checked_integer(value),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+/// Wide integers may be stored as strings (see `wide_integer_impls`), so strings holding
+/// an integer are clamped as well, including those beyond the range of `i128` or `u128`.
+macro_rules! saturating_impls {
+    ($($int:ty),*) => {
+        $(
+            impl SurrealDeserializer for Saturating<$int> {
+                fn deserialize(value: &Value) -> Result<Saturating<$int>, SurrealResponseError> {
+                    let clamp = |v: i128| {
+                        <$int>::try_from(v).unwrap_or(if v < 0 { <$int>::MIN } else { <$int>::MAX })
+                    };
+
+                    let saturated = match value {
+                        Value::Strand(strand) => match strand.as_str().parse::<i128>() {
+                            Ok(v) => clamp(v),
+                            Err(e) if *e.kind() == IntErrorKind::PosOverflow => strand
+                                .as_str()
+                                .parse::<u128>()
+                                .ok()
+                                .and_then(|v| <$int>::try_from(v).ok())
+                                .unwrap_or(<$int>::MAX),
+                            Err(e) if *e.kind() == IntErrorKind::NegOverflow => <$int>::MIN,
+                            Err(_) => {
+                                return Err(
+                                    SurrealResponseError::ExpectedANumberI64.mismatch::<Self>(value),
+                                )
+                            }
+                        },
+                        Value::Number(Number::Int(v)) => clamp(i128::from(*v)),
+                        Value::Number(Number::Float(v)) => *v as $int,
+                        Value::Number(Number::Decimal(v)) => {
+                            clamp(i128::try_from(v.trunc()).unwrap_or_default())
+                        }
+                        _ => {
+                            return Err(
+                                SurrealResponseError::ExpectedANumberI64.mismatch::<Self>(value),
+                            )
+                        }
+                    };
+
+                    Ok(Saturating(saturated))
+                }
+            }
+        )*
+    };
+}
+
+macro_rules! non_zero_impls {
+    ($($non_zero:ty => $int:ty),*) => {
+        $(
+            impl SurrealSerializer for $non_zero {
+                fn serialize(self) -> Value {
+                    self.get().serialize()
+                }
+            }
+
+            impl SurrealDeserializer for $non_zero {
+                fn deserialize(value: &Value) -> Result<$non_zero, SurrealResponseError> {
+                    <$non_zero>::new(<$int>::deserialize(value)?).ok_or_else(|| {
+                        SurrealResponseError::ExpectedANonZeroNumber.mismatch::<$non_zero>(value)
+                    })
+                }
+            }
+        )*
+    };
 }
 
+integer_impls!(i8, i16, i32, i64, isize, u8, u16, u32, u64, usize);
+wide_integer_impls!(i128, u128);
+saturating_impls!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+non_zero_impls!(
+    NonZeroI8 => i8,
+    NonZeroI16 => i16,
+    NonZeroI32 => i32,
+    NonZeroI64 => i64,
+    NonZeroI128 => i128,
+    NonZeroIsize => isize,
+    NonZeroU8 => u8,
+    NonZeroU16 => u16,
+    NonZeroU32 => u32,
+    NonZeroU64 => u64,
+    NonZeroU128 => u128,
+    NonZeroUsize => usize
+);
+copy_serializer_ref_impls!(
+    i8,
+    i16,
+    i32,
+    i64,
+    i128,
+    isize,
+    u8,
+    u16,
+    u32,
+    u64,
+    u128,
+    usize,
+    NonZeroI8,
+    NonZeroI16,
+    NonZeroI32,
+    NonZeroI64,
+    NonZeroI128,
+    NonZeroIsize,
+    NonZeroU8,
+    NonZeroU16,
+    NonZeroU32,
+    NonZeroU64,
+    NonZeroU128,
+    NonZeroUsize,
+    f32,
+    f64,
+    bool,
+    char,
+    Uuid,
+    Duration,
+    DateTime<Utc>
+);
+
 impl SurrealSerializer for f32 {
     fn serialize(self) -> Value {
         Value::from(self as f64)
@@ -98,7 +340,7 @@ impl SurrealDeserializer for f32 {
         if let Value::Number(n) = value {
             Ok(n.as_float() as f32)
         } else {
-            Err(SurrealResponseError::ExpectedANumberF64)
+            Err(SurrealResponseError::ExpectedANumberF64.mismatch::<f32>(value))
         }
     }
 }
@@ -114,7 +356,7 @@ impl SurrealDeserializer for f64 {
         if let Value::Number(n) = value {
             Ok(n.as_float())
         } else {
-            Err(SurrealResponseError::ExpectedANumberF64)
+            Err(SurrealResponseError::ExpectedANumberF64.mismatch::<f64>(value))
         }
     }
 }
@@ -130,7 +372,27 @@ impl SurrealDeserializer for bool {
         if let Value::Bool(b) = value {
             Ok(*b)
         } else {
-            Err(SurrealResponseError::ExpectedABool)
+            Err(SurrealResponseError::ExpectedABool.mismatch::<bool>(value))
+        }
+    }
+}
+
+impl SurrealSerializer for char {
+    fn serialize(self) -> Value {
+        Value::from(self.to_string())
+    }
+}
+
+impl SurrealDeserializer for char {
+    fn deserialize(value: &Value) -> Result<char, SurrealResponseError> {
+        let Value::Strand(strand) = value else {
+            return Err(SurrealResponseError::ExpectedAStrand.mismatch::<char>(value));
+        };
+
+        let mut chars = strand.as_str().chars();
+        match (chars.next(), chars.next()) {
+            (Some(c), None) => Ok(c),
+            _ => Err(SurrealResponseError::ExpectedAChar.mismatch::<char>(value)),
         }
     }
 }
@@ -144,160 +406,1456 @@ where
     }
 }
 
+impl<T> SurrealSerializerRef for Vec<T>
+where
+    T: SurrealSerializerRef,
+{
+    fn serialize_ref(&self) -> Value {
+        self.as_slice().serialize_ref()
+    }
+}
+
+impl<T> SurrealSerializerRef for [T]
+where
+    T: SurrealSerializerRef,
+{
+    fn serialize_ref(&self) -> Value {
+        Value::Array(self.iter().map(|item| item.serialize_ref()).collect())
+    }
+}
+
 impl<T> SurrealDeserializer for Vec<T>
 where
     T: SurrealDeserializer,
 {
     fn deserialize(value: &Value) -> Result<Vec<T>, SurrealResponseError> {
-        if let Value::Array(array) = value {
-            array.iter().map(T::deserialize).collect()
-        } else {
-            Err(SurrealResponseError::ExpectedAnArray)
-        }
+        deserialize_sequence(value)
     }
 }
 
-impl<T> SurrealSerializer for Option<T>
+/// Collect the items of an array into `C`, recording the index of a failing item.
+pub(crate) fn deserialize_sequence<T, C>(value: &Value) -> Result<C, SurrealResponseError>
 where
-    T: SurrealSerializer,
+    T: SurrealDeserializer,
+    C: FromIterator<T>,
+{
+    if let Value::Array(array) = value {
+        array
+            .iter()
+            .enumerate()
+            .map(|(index, item)| T::deserialize(item).map_err(|e| e.at_index(index)))
+            .collect()
+    } else {
+        Err(SurrealResponseError::ExpectedAnArray.mismatch::<C>(value))
+    }
+}
+
+/// Sequences that are stored as an array in iteration order.
+macro_rules! sequence_impls {
+    ($($ty:ident),*) => {
+        $(
+            impl<T> SurrealSerializer for $ty<T>
+            where
+                T: SurrealSerializer,
+            {
+                fn serialize(self) -> Value {
+                    Value::Array(self.into_iter().map(|item| item.serialize()).collect())
+                }
+            }
+
+            impl<T> SurrealSerializerRef for $ty<T>
+            where
+                T: SurrealSerializerRef,
+            {
+                fn serialize_ref(&self) -> Value {
+                    Value::Array(self.iter().map(|item| item.serialize_ref()).collect())
+                }
+            }
+
+            impl<T> SurrealDeserializer for $ty<T>
+            where
+                T: SurrealDeserializer,
+            {
+                fn deserialize(value: &Value) -> Result<$ty<T>, SurrealResponseError> {
+                    deserialize_sequence(value)
+                }
+            }
+        )*
+    };
+}
+
+sequence_impls!(VecDeque, LinkedList);
+
+// A heap has no meaningful iteration order, so it is stored sorted in ascending order.
+impl<T> SurrealSerializer for BinaryHeap<T>
+where
+    T: SurrealSerializer + Ord,
 {
     fn serialize(self) -> Value {
-        match self {
-            Some(value) => value.serialize(),
-            None => Value::None,
-        }
+        self.into_sorted_vec().serialize()
     }
 }
 
-impl<T> SurrealDeserializer for Option<T>
+impl<T> SurrealSerializerRef for BinaryHeap<T>
 where
-    T: SurrealDeserializer,
+    T: SurrealSerializerRef + Ord,
 {
-    fn deserialize(value: &Value) -> Result<Option<T>, SurrealResponseError> {
-        if value.is_none() {
-            Ok(None)
-        } else {
-            let result = T::deserialize(value);
-            match result {
-                Ok(it) => Ok(Some(it)),
-                Err(e) => match e {
-                    SurrealResponseError::CannotReadNoneValue => Ok(None),
-                    e => Err(e),
-                },
-            }
-        }
+    fn serialize_ref(&self) -> Value {
+        let mut items: Vec<&T> = self.iter().collect();
+        items.sort();
+        items.serialize_ref()
     }
 }
 
-// Example implementation for String
-impl SurrealSerializer for String {
-    fn serialize(self) -> Value {
-        Value::from(self.clone())
+impl<T> SurrealDeserializer for BinaryHeap<T>
+where
+    T: SurrealDeserializer + Ord,
+{
+    fn deserialize(value: &Value) -> Result<BinaryHeap<T>, SurrealResponseError> {
+        deserialize_sequence(value)
     }
 }
 
-impl SurrealDeserializer for String {
-    fn deserialize(value: &Value) -> Result<String, SurrealResponseError> {
-        if let Value::Strand(s) = value {
-            Ok(s.0.clone())
-        } else {
-            Err(SurrealResponseError::ExpectedAStrand)
-        }
+/// A type that can be used as the key of a map stored as an object.
+pub trait ObjectKey
+where
+    Self: Sized,
+{
+    fn to_key(&self) -> String;
+
+    fn from_key(key: &str) -> Result<Self, SurrealResponseError>;
+}
+
+impl ObjectKey for String {
+    fn to_key(&self) -> String {
+        self.clone()
+    }
+
+    fn from_key(key: &str) -> Result<Self, SurrealResponseError> {
+        Ok(key.to_owned())
     }
 }
 
-// Example implementations for Uuid and Duration
-impl SurrealSerializer for Uuid {
-    fn serialize(self) -> Value {
-        Value::from(self.to_string())
+impl ObjectKey for char {
+    fn to_key(&self) -> String {
+        self.to_string()
+    }
+
+    fn from_key(key: &str) -> Result<Self, SurrealResponseError> {
+        char::deserialize(&Value::from(key))
     }
 }
 
-impl SurrealDeserializer for Uuid {
-    fn deserialize(value: &Value) -> Result<Uuid, SurrealResponseError> {
-        if let Value::Uuid(uuid) = value {
-            Ok(uuid.0)
-        } else {
-            Err(SurrealResponseError::ExpectedAUuid)
+impl ObjectKey for Uuid {
+    fn to_key(&self) -> String {
+        self.to_string()
+    }
+
+    fn from_key(key: &str) -> Result<Self, SurrealResponseError> {
+        Uuid::deserialize(&Value::from(key))
+    }
+}
+
+macro_rules! integer_key_impls {
+    ($($int:ty),*) => {
+        $(
+            impl ObjectKey for $int {
+                fn to_key(&self) -> String {
+                    self.to_string()
+                }
+
+                fn from_key(key: &str) -> Result<Self, SurrealResponseError> {
+                    integer_from_strand(&Value::from(key), key)
+                }
+            }
+        )*
+    };
+}
+
+integer_key_impls!(i8, i16, i32, i64, i128, isize, u8, u16, u32, u64, u128, usize);
+
+pub(crate) fn serialize_map<I>(entries: I) -> Value
+where
+    I: IntoIterator<Item = (String, Value)>,
+{
+    Value::Object(Object::from(
+        entries.into_iter().collect::<BTreeMap<String, Value>>(),
+    ))
+}
+
+pub(crate) fn deserialize_map<K, V, M>(value: &Value) -> Result<M, SurrealResponseError>
+where
+    K: ObjectKey,
+    V: SurrealDeserializer,
+    M: FromIterator<(K, V)>,
+{
+    let Value::Object(object) = value else {
+        return Err(SurrealResponseError::ExpectedAnObject.mismatch::<M>(value));
+    };
+
+    object
+        .iter()
+        .map(|(key, item)| {
+            let k = K::from_key(key).map_err(|e| e.at_field(key.as_str()))?;
+            let v = V::deserialize(item).map_err(|e| e.at_field(key.as_str()))?;
+            Ok((k, v))
+        })
+        .collect()
+}
+
+/// Deserialize an array into a set, `insert` must return false when the item is already present.
+pub(crate) fn deserialize_set<T, S>(
+    value: &Value,
+    mut insert: impl FnMut(&mut S, T) -> bool,
+) -> Result<S, SurrealResponseError>
+where
+    T: SurrealDeserializer,
+    S: Default,
+{
+    let Value::Array(array) = value else {
+        return Err(SurrealResponseError::ExpectedAnArray.mismatch::<S>(value));
+    };
+
+    let mut set = S::default();
+    for (index, item) in array.iter().enumerate() {
+        let item = T::deserialize(item).map_err(|e| e.at_index(index))?;
+        if !insert(&mut set, item) {
+            return Err(SurrealResponseError::ExpectedASet
+                .mismatch::<S>(value)
+                .at_index(index));
         }
     }
+
+    Ok(set)
 }
 
-impl SurrealSerializer for Duration {
+impl<K, V, S> SurrealSerializer for HashMap<K, V, S>
+where
+    K: ObjectKey,
+    V: SurrealSerializer,
+{
     fn serialize(self) -> Value {
-        Value::from(self)
+        serialize_map(
+            self.into_iter()
+                .map(|(key, value)| (key.to_key(), value.serialize())),
+        )
     }
 }
 
-impl SurrealDeserializer for Duration {
-    fn deserialize(value: &Value) -> Result<Duration, SurrealResponseError> {
-        if let Value::Duration(duration) = value {
-            Ok(duration.0)
-        } else {
-            Err(SurrealResponseError::ExpectedADuration)
-        }
+impl<K, V, S> SurrealSerializerRef for HashMap<K, V, S>
+where
+    K: ObjectKey,
+    V: SurrealSerializerRef,
+{
+    fn serialize_ref(&self) -> Value {
+        serialize_map(
+            self.iter()
+                .map(|(key, value)| (key.to_key(), value.serialize_ref())),
+        )
     }
 }
 
-// Implementation for chrono::DateTime<Utc>
-impl SurrealSerializer for DateTime<Utc> {
+impl<K, V, S> SurrealDeserializer for HashMap<K, V, S>
+where
+    K: ObjectKey + Eq + Hash,
+    V: SurrealDeserializer,
+    S: BuildHasher + Default,
+{
+    fn deserialize(value: &Value) -> Result<Self, SurrealResponseError> {
+        deserialize_map(value)
+    }
+}
+
+impl<K, V> SurrealSerializer for BTreeMap<K, V>
+where
+    K: ObjectKey,
+    V: SurrealSerializer,
+{
     fn serialize(self) -> Value {
-        Value::from(self)
+        serialize_map(
+            self.into_iter()
+                .map(|(key, value)| (key.to_key(), value.serialize())),
+        )
     }
 }
 
-impl SurrealDeserializer for DateTime<Utc> {
-    fn deserialize(value: &Value) -> Result<DateTime<Utc>, SurrealResponseError> {
-        if let Value::Datetime(datetime) = value {
-            Ok(datetime.0)
-        } else {
-            Err(SurrealResponseError::ExpectedADatetime)
-        }
+impl<K, V> SurrealSerializerRef for BTreeMap<K, V>
+where
+    K: ObjectKey,
+    V: SurrealSerializerRef,
+{
+    fn serialize_ref(&self) -> Value {
+        serialize_map(
+            self.iter()
+                .map(|(key, value)| (key.to_key(), value.serialize_ref())),
+        )
     }
 }
 
-impl<T> SurrealSerializer for Box<T>
+impl<K, V> SurrealDeserializer for BTreeMap<K, V>
+where
+    K: ObjectKey + Ord,
+    V: SurrealDeserializer,
+{
+    fn deserialize(value: &Value) -> Result<Self, SurrealResponseError> {
+        deserialize_map(value)
+    }
+}
+
+impl<T, S> SurrealSerializer for HashSet<T, S>
 where
     T: SurrealSerializer,
 {
     fn serialize(self) -> Value {
-        (*self).serialize()
+        Value::Array(self.into_iter().map(|item| item.serialize()).collect())
     }
 }
 
-impl<T> SurrealDeserializer for Box<T>
+impl<T, S> SurrealSerializerRef for HashSet<T, S>
 where
-    T: SurrealDeserializer,
+    T: SurrealSerializerRef,
 {
-    fn deserialize(value: &Value) -> Result<Box<T>, SurrealResponseError> {
-        Ok(Box::new(T::deserialize(value)?))
+    fn serialize_ref(&self) -> Value {
+        Value::Array(self.iter().map(|item| item.serialize_ref()).collect())
     }
 }
 
-impl SurrealSerializer for Thing {
+impl<T, S> SurrealDeserializer for HashSet<T, S>
+where
+    T: SurrealDeserializer + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn deserialize(value: &Value) -> Result<Self, SurrealResponseError> {
+        deserialize_set(value, |set: &mut Self, item| set.insert(item))
+    }
+}
+
+impl<T> SurrealSerializer for BTreeSet<T>
+where
+    T: SurrealSerializer,
+{
     fn serialize(self) -> Value {
-        Value::from(self)
+        Value::Array(self.into_iter().map(|item| item.serialize()).collect())
     }
 }
 
-impl SurrealDeserializer for Thing {
-    fn deserialize(value: &Value) -> Result<Thing, SurrealResponseError> {
-        if let Value::Thing(thing) = value {
-            Ok(thing.clone())
-        } else {
-            Err(SurrealResponseError::ExpectedAThing)
+impl<T> SurrealSerializerRef for BTreeSet<T>
+where
+    T: SurrealSerializerRef,
+{
+    fn serialize_ref(&self) -> Value {
+        Value::Array(self.iter().map(|item| item.serialize_ref()).collect())
+    }
+}
+
+impl<T> SurrealDeserializer for BTreeSet<T>
+where
+    T: SurrealDeserializer + Ord,
+{
+    fn deserialize(value: &Value) -> Result<Self, SurrealResponseError> {
+        deserialize_set(value, |set: &mut Self, item| set.insert(item))
+    }
+}
+
+/// Borrow the items of `value`, which must be an array of exactly `len` items.
+fn exact_array<T>(value: &Value, len: usize) -> Result<&[Value], SurrealResponseError> {
+    let Value::Array(array) = value else {
+        return Err(SurrealResponseError::ExpectedAnArray.mismatch::<T>(value));
+    };
+
+    if array.len() != len {
+        return Err(SurrealResponseError::ArrayLengthMismatch {
+            expected: len,
+            found: array.len(),
         }
+        .mismatch::<T>(value));
     }
+
+    Ok(&array.0)
 }
 
-impl SurrealSerializer for Value {
+impl<T, const N: usize> SurrealSerializer for [T; N]
+where
+    T: SurrealSerializer,
+{
     fn serialize(self) -> Value {
-        self
+        Value::Array(self.into_iter().map(|item| item.serialize()).collect())
     }
 }
 
-impl SurrealDeserializer for Value {
+impl<T, const N: usize> SurrealSerializerRef for [T; N]
+where
+    T: SurrealSerializerRef,
+{
+    fn serialize_ref(&self) -> Value {
+        self.as_slice().serialize_ref()
+    }
+}
+
+impl<T, const N: usize> SurrealDeserializer for [T; N]
+where
+    T: SurrealDeserializer,
+{
+    fn deserialize(value: &Value) -> Result<[T; N], SurrealResponseError> {
+        let items = exact_array::<Self>(value, N)?
+            .iter()
+            .enumerate()
+            .map(|(index, item)| T::deserialize(item).map_err(|e| e.at_index(index)))
+            .collect::<Result<Vec<T>, SurrealResponseError>>()?;
+
+        items.try_into().map_err(|items: Vec<T>| {
+            SurrealResponseError::ArrayLengthMismatch {
+                expected: N,
+                found: items.len(),
+            }
+            .mismatch::<Self>(value)
+        })
+    }
+}
+
+macro_rules! tuple_impls {
+    ($($len:literal => ($($name:ident $index:tt),+)),+) => {
+        $(
+            impl<$($name),+> SurrealSerializer for ($($name,)+)
+            where
+                $($name: SurrealSerializer,)+
+            {
+                fn serialize(self) -> Value {
+                    Value::Array(vec![$(self.$index.serialize()),+].into())
+                }
+            }
+
+            impl<$($name),+> SurrealSerializerRef for ($($name,)+)
+            where
+                $($name: SurrealSerializerRef,)+
+            {
+                fn serialize_ref(&self) -> Value {
+                    Value::Array(vec![$(self.$index.serialize_ref()),+].into())
+                }
+            }
+
+            impl<$($name),+> SurrealDeserializer for ($($name,)+)
+            where
+                $($name: SurrealDeserializer,)+
+            {
+                fn deserialize(value: &Value) -> Result<Self, SurrealResponseError> {
+                    let items = exact_array::<Self>(value, $len)?;
+                    Ok(($(
+                        $name::deserialize(&items[$index]).map_err(|e| e.at_index($index))?,
+                    )+))
+                }
+            }
+        )+
+    };
+}
+
+tuple_impls!(
+    1 => (T0 0),
+    2 => (T0 0, T1 1),
+    3 => (T0 0, T1 1, T2 2),
+    4 => (T0 0, T1 1, T2 2, T3 3),
+    5 => (T0 0, T1 1, T2 2, T3 3, T4 4),
+    6 => (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5),
+    7 => (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6),
+    8 => (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7),
+    9 => (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8),
+    10 => (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9),
+    11 => (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10),
+    12 => (T0 0, T1 1, T2 2, T3 3, T4 4, T5 5, T6 6, T7 7, T8 8, T9 9, T10 10, T11 11)
+);
+
+impl<T> SurrealSerializer for Option<T>
+where
+    T: SurrealSerializer,
+{
+    fn serialize(self) -> Value {
+        match self {
+            Some(value) => value.serialize(),
+            None => Value::None,
+        }
+    }
+}
+
+impl<T> SurrealSerializerRef for Option<T>
+where
+    T: SurrealSerializerRef,
+{
+    fn serialize_ref(&self) -> Value {
+        match self {
+            Some(value) => value.serialize_ref(),
+            None => Value::None,
+        }
+    }
+}
+
+impl<T> SurrealDeserializer for Option<T>
+where
+    T: SurrealDeserializer,
+{
+    fn deserialize(value: &Value) -> Result<Option<T>, SurrealResponseError> {
+        if matches!(value, Value::None | Value::Null) {
+            Ok(None)
+        } else {
+            let result = T::deserialize(value);
+            match result {
+                Ok(it) => Ok(Some(it)),
+                Err(e) => match e.kind() {
+                    SurrealResponseError::CannotReadNoneValue => Ok(None),
+                    _ => Err(e),
+                },
+            }
+        }
+    }
+}
+
+// Results are stored as an externally tagged enum, `{ ok: value }` or `{ err: error }`.
+impl<T, E> SurrealSerializer for Result<T, E>
+where
+    T: SurrealSerializer,
+    E: SurrealSerializer,
+{
+    fn serialize(self) -> Value {
+        match self {
+            Ok(value) => {
+                EnumRepr::External.serialize("ok", VariantContent::Newtype(value.serialize()))
+            }
+            Err(error) => {
+                EnumRepr::External.serialize("err", VariantContent::Newtype(error.serialize()))
+            }
+        }
+    }
+}
+
+impl<T, E> SurrealSerializerRef for Result<T, E>
+where
+    T: SurrealSerializerRef,
+    E: SurrealSerializerRef,
+{
+    fn serialize_ref(&self) -> Value {
+        match self {
+            Ok(value) => {
+                EnumRepr::External.serialize("ok", VariantContent::Newtype(value.serialize_ref()))
+            }
+            Err(error) => {
+                EnumRepr::External.serialize("err", VariantContent::Newtype(error.serialize_ref()))
+            }
+        }
+    }
+}
+
+impl<T, E> SurrealDeserializer for Result<T, E>
+where
+    T: SurrealDeserializer,
+    E: SurrealDeserializer,
+{
+    fn deserialize(value: &Value) -> Result<Result<T, E>, SurrealResponseError> {
+        let tagged = EnumRepr::External.deserialize::<Self>(value)?;
+        match tagged.variant {
+            "ok" => Ok(Ok(tagged.newtype()?)),
+            "err" => Ok(Err(tagged.newtype()?)),
+            _ => Err(tagged.unknown_variant::<Self>()),
+        }
+    }
+}
+
+/// An untagged union of two types, stored as the value of whichever one it holds.
+///
+/// `Left` is tried first when reading, so it should be the more specific of the two types.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash, serde::Serialize, serde::Deserialize)]
+#[serde(untagged)]
+pub enum Either<L, R> {
+    Left(L),
+    Right(R),
+}
+
+impl<L, R> SurrealSerializer for Either<L, R>
+where
+    L: SurrealSerializer,
+    R: SurrealSerializer,
+{
+    fn serialize(self) -> Value {
+        match self {
+            Either::Left(value) => value.serialize(),
+            Either::Right(value) => value.serialize(),
+        }
+    }
+}
+
+impl<L, R> SurrealSerializerRef for Either<L, R>
+where
+    L: SurrealSerializerRef,
+    R: SurrealSerializerRef,
+{
+    fn serialize_ref(&self) -> Value {
+        match self {
+            Either::Left(value) => value.serialize_ref(),
+            Either::Right(value) => value.serialize_ref(),
+        }
+    }
+}
+
+impl<L, R> SurrealDeserializer for Either<L, R>
+where
+    L: SurrealDeserializer,
+    R: SurrealDeserializer,
+{
+    fn deserialize(value: &Value) -> Result<Either<L, R>, SurrealResponseError> {
+        let left = match L::deserialize(value) {
+            Ok(left) => return Ok(Either::Left(left)),
+            Err(e) => e,
+        };
+
+        match R::deserialize(value) {
+            Ok(right) => Ok(Either::Right(right)),
+            Err(right) => {
+                Err(SurrealResponseError::NoMatchingVariant(vec![left, right])
+                    .mismatch::<Self>(value))
+            }
+        }
+    }
+}
+
+// Example implementation for String
+impl SurrealSerializer for String {
+    fn serialize(self) -> Value {
+        Value::from(self.clone())
+    }
+}
+
+impl SurrealSerializerRef for String {
+    fn serialize_ref(&self) -> Value {
+        self.as_str().serialize_ref()
+    }
+}
+
+impl SurrealSerializerRef for str {
+    fn serialize_ref(&self) -> Value {
+        Value::from(self)
+    }
+}
+
+impl SurrealDeserializer for String {
+    fn deserialize(value: &Value) -> Result<String, SurrealResponseError> {
+        if let Value::Strand(s) = value {
+            Ok(s.0.clone())
+        } else {
+            Err(SurrealResponseError::ExpectedAStrand.mismatch::<String>(value))
+        }
+    }
+}
+
+/// Parse a string with the `FromStr` implementation of `T`.
+pub(crate) fn parse_strand<T>(value: &Value) -> Result<T, SurrealResponseError>
+where
+    T: FromStr,
+    T::Err: Display,
+{
+    match value {
+        Value::Strand(strand) => strand.as_str().parse().map_err(|e: T::Err| {
+            SurrealResponseError::ParseError(e.to_string()).mismatch::<T>(value)
+        }),
+        _ => Err(SurrealResponseError::ExpectedAStrand.mismatch::<T>(value)),
+    }
+}
+
+/// Types that are stored as the string of their `Display` implementation.
+macro_rules! display_impls {
+    ($($ty:ty),*) => {
+        $(
+            impl SurrealSerializer for $ty {
+                fn serialize(self) -> Value {
+                    self.serialize_ref()
+                }
+            }
+
+            impl SurrealSerializerRef for $ty {
+                fn serialize_ref(&self) -> Value {
+                    Value::from(self.to_string())
+                }
+            }
+
+            impl SurrealDeserializer for $ty {
+                fn deserialize(value: &Value) -> Result<$ty, SurrealResponseError> {
+                    parse_strand(value)
+                }
+            }
+        )*
+    };
+}
+
+display_impls!(
+    IpAddr,
+    Ipv4Addr,
+    Ipv6Addr,
+    SocketAddr,
+    SocketAddrV4,
+    SocketAddrV6
+);
+
+// Paths that are not valid UTF-8 are stored lossily, with the invalid sequences replaced.
+impl SurrealSerializer for PathBuf {
+    fn serialize(self) -> Value {
+        self.as_path().serialize_ref()
+    }
+}
+
+impl SurrealSerializerRef for PathBuf {
+    fn serialize_ref(&self) -> Value {
+        self.as_path().serialize_ref()
+    }
+}
+
+impl SurrealSerializerRef for Path {
+    fn serialize_ref(&self) -> Value {
+        Value::from(self.to_string_lossy().into_owned())
+    }
+}
+
+impl SurrealDeserializer for PathBuf {
+    fn deserialize(value: &Value) -> Result<PathBuf, SurrealResponseError> {
+        match value {
+            Value::Strand(strand) => Ok(PathBuf::from(strand.as_str())),
+            _ => Err(SurrealResponseError::ExpectedAStrand.mismatch::<PathBuf>(value)),
+        }
+    }
+}
+
+// Example implementations for Uuid and Duration
+impl SurrealSerializer for Uuid {
+    fn serialize(self) -> Value {
+        Value::Uuid(self.into())
+    }
+}
+
+impl SurrealDeserializer for Uuid {
+    fn deserialize(value: &Value) -> Result<Uuid, SurrealResponseError> {
+        match value {
+            Value::Uuid(uuid) => Ok(uuid.0),
+            Value::Strand(strand) => Uuid::parse_str(strand.as_str())
+                .map_err(|_| SurrealResponseError::ExpectedAUuid.mismatch::<Uuid>(value)),
+            _ => Err(SurrealResponseError::ExpectedAUuid.mismatch::<Uuid>(value)),
+        }
+    }
+}
+
+impl SurrealSerializer for Duration {
+    fn serialize(self) -> Value {
+        Value::from(self)
+    }
+}
+
+impl SurrealDeserializer for Duration {
+    fn deserialize(value: &Value) -> Result<Duration, SurrealResponseError> {
+        if let Value::Duration(duration) = value {
+            Ok(duration.0)
+        } else {
+            Err(SurrealResponseError::ExpectedADuration.mismatch::<Duration>(value))
+        }
+    }
+}
+
+/// Read a datetime from a native datetime or an RFC 3339 string such as `2024-01-31T10:00:00+07:00`.
+fn datetime_utc<T>(value: &Value) -> Result<DateTime<Utc>, SurrealResponseError> {
+    match value {
+        Value::Datetime(datetime) => Ok(datetime.0),
+        Value::Strand(strand) => DateTime::parse_from_rfc3339(strand.as_str())
+            .map(|datetime| datetime.to_utc())
+            .map_err(|_| SurrealResponseError::ExpectedADatetime.mismatch::<T>(value)),
+        _ => Err(SurrealResponseError::ExpectedADatetime.mismatch::<T>(value)),
+    }
+}
+
+// Implementation for chrono::DateTime<Utc>
+impl SurrealSerializer for DateTime<Utc> {
+    fn serialize(self) -> Value {
+        Value::from(self)
+    }
+}
+
+impl SurrealDeserializer for DateTime<Utc> {
+    fn deserialize(value: &Value) -> Result<DateTime<Utc>, SurrealResponseError> {
+        datetime_utc::<Self>(value)
+    }
+}
+
+/// Stored as a datetime in UTC, the offset is not kept. Reading an RFC 3339 string keeps its offset.
+impl SurrealSerializer for DateTime<FixedOffset> {
+    fn serialize(self) -> Value {
+        self.to_utc().serialize()
+    }
+}
+
+impl SurrealDeserializer for DateTime<FixedOffset> {
+    fn deserialize(value: &Value) -> Result<DateTime<FixedOffset>, SurrealResponseError> {
+        if let Value::Strand(strand) = value {
+            if let Ok(datetime) = DateTime::parse_from_rfc3339(strand.as_str()) {
+                return Ok(datetime);
+            }
+        }
+
+        Ok(datetime_utc::<Self>(value)?.fixed_offset())
+    }
+}
+
+/// Stored as a datetime in UTC, converted back to the local timezone on read.
+impl SurrealSerializer for DateTime<Local> {
+    fn serialize(self) -> Value {
+        self.to_utc().serialize()
+    }
+}
+
+impl SurrealDeserializer for DateTime<Local> {
+    fn deserialize(value: &Value) -> Result<DateTime<Local>, SurrealResponseError> {
+        Ok(datetime_utc::<Self>(value)?.with_timezone(&Local))
+    }
+}
+
+/// Stored as a datetime, taking the naive datetime as UTC.
+/// Also reads strings without an offset such as `2024-01-31T10:00:00`.
+impl SurrealSerializer for NaiveDateTime {
+    fn serialize(self) -> Value {
+        self.and_utc().serialize()
+    }
+}
+
+impl SurrealDeserializer for NaiveDateTime {
+    fn deserialize(value: &Value) -> Result<NaiveDateTime, SurrealResponseError> {
+        if let Value::Strand(strand) = value {
+            if let Ok(datetime) = NaiveDateTime::from_str(strand.as_str()) {
+                return Ok(datetime);
+            }
+        }
+
+        Ok(datetime_utc::<Self>(value)?.naive_utc())
+    }
+}
+
+/// Stored as a datetime at midnight UTC. Also reads date strings such as `2024-01-31`.
+impl SurrealSerializer for NaiveDate {
+    fn serialize(self) -> Value {
+        self.and_time(NaiveTime::MIN).serialize()
+    }
+}
+
+impl SurrealDeserializer for NaiveDate {
+    fn deserialize(value: &Value) -> Result<NaiveDate, SurrealResponseError> {
+        if let Value::Strand(strand) = value {
+            if let Ok(date) = NaiveDate::from_str(strand.as_str()) {
+                return Ok(date);
+            }
+        }
+
+        Ok(datetime_utc::<Self>(value)?.date_naive())
+    }
+}
+
+/// Stored as an ISO 8601 time string such as `10:30:00.250`, since SurrealDB has no time type.
+/// Also reads the UTC time of a datetime.
+impl SurrealSerializer for NaiveTime {
+    fn serialize(self) -> Value {
+        Value::from(self.to_string())
+    }
+}
+
+impl SurrealDeserializer for NaiveTime {
+    fn deserialize(value: &Value) -> Result<NaiveTime, SurrealResponseError> {
+        match value {
+            Value::Strand(strand) => NaiveTime::from_str(strand.as_str())
+                .map_err(|_| SurrealResponseError::ExpectedADatetime.mismatch::<Self>(value)),
+            _ => Ok(datetime_utc::<Self>(value)?.time()),
+        }
+    }
+}
+
+copy_serializer_ref_impls!(
+    DateTime<FixedOffset>,
+    DateTime<Local>,
+    NaiveDateTime,
+    NaiveDate,
+    NaiveTime
+);
+
+impl<T> SurrealSerializer for Box<T>
+where
+    T: SurrealSerializer,
+{
+    fn serialize(self) -> Value {
+        (*self).serialize()
+    }
+}
+
+impl<T> SurrealSerializerRef for Box<T>
+where
+    T: SurrealSerializerRef,
+{
+    fn serialize_ref(&self) -> Value {
+        (**self).serialize_ref()
+    }
+}
+
+impl<T> SurrealDeserializer for Box<T>
+where
+    T: SurrealDeserializer,
+{
+    fn deserialize(value: &Value) -> Result<Box<T>, SurrealResponseError> {
+        Ok(Box::new(T::deserialize(value)?))
+    }
+}
+
+impl<T> SurrealSerializer for Box<[T]>
+where
+    T: SurrealSerializer,
+{
+    fn serialize(self) -> Value {
+        self.into_vec().serialize()
+    }
+}
+
+impl<T> SurrealSerializerRef for Box<[T]>
+where
+    T: SurrealSerializerRef,
+{
+    fn serialize_ref(&self) -> Value {
+        (**self).serialize_ref()
+    }
+}
+
+impl<T> SurrealDeserializer for Box<[T]>
+where
+    T: SurrealDeserializer,
+{
+    fn deserialize(value: &Value) -> Result<Box<[T]>, SurrealResponseError> {
+        deserialize_sequence(value)
+    }
+}
+
+// Shared pointers cannot be moved out of, so they always serialize through the reference.
+macro_rules! shared_pointer_impls {
+    ($($ty:ident),*) => {
+        $(
+            impl<T> SurrealSerializer for $ty<T>
+            where
+                T: SurrealSerializerRef + ?Sized,
+            {
+                fn serialize(self) -> Value {
+                    (*self).serialize_ref()
+                }
+            }
+
+            impl<T> SurrealSerializerRef for $ty<T>
+            where
+                T: SurrealSerializerRef + ?Sized,
+            {
+                fn serialize_ref(&self) -> Value {
+                    (**self).serialize_ref()
+                }
+            }
+
+            impl<T> SurrealDeserializer for $ty<T>
+            where
+                T: SurrealDeserializer,
+            {
+                fn deserialize(value: &Value) -> Result<$ty<T>, SurrealResponseError> {
+                    Ok($ty::new(T::deserialize(value)?))
+                }
+            }
+        )*
+    };
+}
+
+shared_pointer_impls!(Arc, Rc);
+
+impl<B> SurrealSerializer for Cow<'_, B>
+where
+    B: SurrealSerializerRef + ToOwned + ?Sized,
+{
+    fn serialize(self) -> Value {
+        (*self).serialize_ref()
+    }
+}
+
+impl<B> SurrealSerializerRef for Cow<'_, B>
+where
+    B: SurrealSerializerRef + ToOwned + ?Sized,
+{
+    fn serialize_ref(&self) -> Value {
+        (**self).serialize_ref()
+    }
+}
+
+impl<B> SurrealDeserializer for Cow<'_, B>
+where
+    B: ToOwned + ?Sized,
+    B::Owned: SurrealDeserializer,
+{
+    fn deserialize(value: &Value) -> Result<Self, SurrealResponseError> {
+        Ok(Cow::Owned(B::Owned::deserialize(value)?))
+    }
+}
+
+impl SurrealSerializer for &str {
+    fn serialize(self) -> Value {
+        Value::from(self)
+    }
+}
+
+impl SurrealSerializer for Thing {
+    fn serialize(self) -> Value {
+        Value::from(self)
+    }
+}
+
+impl SurrealSerializerRef for Thing {
+    fn serialize_ref(&self) -> Value {
+        Value::from(self.clone())
+    }
+}
+
+impl SurrealDeserializer for Thing {
+    fn deserialize(value: &Value) -> Result<Thing, SurrealResponseError> {
+        if let Value::Thing(thing) = value {
+            Ok(thing.clone())
+        } else {
+            Err(SurrealResponseError::ExpectedAThing.mismatch::<Thing>(value))
+        }
+    }
+}
+
+impl SurrealSerializer for Value {
+    fn serialize(self) -> Value {
+        self
+    }
+}
+
+impl SurrealSerializerRef for Value {
+    fn serialize_ref(&self) -> Value {
+        self.clone()
+    }
+}
+
+impl SurrealDeserializer for Value {
     fn deserialize(value: &Value) -> Result<Value, SurrealResponseError> {
         Ok(value.clone())
     }
-}
\ No newline at end of file
+}
+
+/// Accepts standard base64 with or without padding, the way SurrealDB prints bytes.
+pub(crate) const BASE64: GeneralPurpose = GeneralPurpose::new(
+    &alphabet::STANDARD,
+    GeneralPurposeConfig::new().with_decode_padding_mode(DecodePaddingMode::Indifferent),
+);
+
+/// Read binary data from native bytes or from a base64 string.
+pub(crate) fn bytes_from_value<T>(value: &Value) -> Result<Vec<u8>, SurrealResponseError> {
+    match value {
+        Value::Bytes(bytes) => Ok(bytes.to_vec()),
+        Value::Strand(strand) => BASE64
+            .decode(strand.as_str())
+            .map_err(|_| SurrealResponseError::ExpectedABytes.mismatch::<T>(value)),
+        _ => Err(SurrealResponseError::ExpectedABytes.mismatch::<T>(value)),
+    }
+}
+
+/// Binary data stored as native bytes.
+///
+/// `Vec<u8>` and `Box<[u8]>` go through the generic sequence impls and stay arrays of numbers,
+/// so binary fields should be declared as `ByteBuf`, which converts from and into both.
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Default, serde::Serialize, serde::Deserialize)]
+#[serde(transparent)]
+pub struct ByteBuf(pub Vec<u8>);
+
+impl Deref for ByteBuf {
+    type Target = Vec<u8>;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl DerefMut for ByteBuf {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl From<Vec<u8>> for ByteBuf {
+    fn from(value: Vec<u8>) -> Self {
+        Self(value)
+    }
+}
+
+impl From<ByteBuf> for Vec<u8> {
+    fn from(value: ByteBuf) -> Self {
+        value.0
+    }
+}
+
+impl From<Box<[u8]>> for ByteBuf {
+    fn from(value: Box<[u8]>) -> Self {
+        Self(value.into_vec())
+    }
+}
+
+impl From<ByteBuf> for Box<[u8]> {
+    fn from(value: ByteBuf) -> Self {
+        value.0.into_boxed_slice()
+    }
+}
+
+impl SurrealSerializer for ByteBuf {
+    fn serialize(self) -> Value {
+        Value::Bytes(Bytes::from(self.0))
+    }
+}
+
+impl SurrealSerializerRef for ByteBuf {
+    fn serialize_ref(&self) -> Value {
+        self.clone().serialize()
+    }
+}
+
+impl SurrealDeserializer for ByteBuf {
+    fn deserialize(value: &Value) -> Result<ByteBuf, SurrealResponseError> {
+        Ok(ByteBuf(bytes_from_value::<Self>(value)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn round_trip<T>(value: T)
+    where
+        T: SurrealSerializer
+            + SurrealSerializerRef
+            + SurrealDeserializer
+            + Clone
+            + PartialEq
+            + std::fmt::Debug,
+    {
+        assert_eq!(value.serialize_ref(), value.clone().serialize());
+        assert_eq!(T::deserialize(&value.clone().serialize()), Ok(value));
+    }
+
+    #[test]
+    fn test_round_trip() {
+        round_trip(-8i8);
+        round_trip(-16i16);
+        round_trip(-32i32);
+        round_trip(i64::MIN);
+        round_trip(i128::MAX);
+        round_trip(-1isize);
+        round_trip(8u8);
+        round_trip(16u16);
+        round_trip(u32::MAX);
+        round_trip(u64::MAX);
+        round_trip(u128::MAX);
+        round_trip(usize::MAX);
+        round_trip(NonZeroU32::new(1).unwrap());
+        round_trip(Saturating(7i16));
+        round_trip(Saturating(u128::MAX));
+        round_trip(Saturating(i128::MIN));
+        round_trip(1.5f32);
+        round_trip(2.5f64);
+        round_trip(true);
+        round_trip('c');
+        round_trip("text".to_owned());
+        round_trip(Uuid::from_u128(0x1234));
+        round_trip(Duration::from_millis(1500));
+        round_trip(DateTime::<Utc>::from_timestamp(1_700_000_000, 0).unwrap());
+        round_trip(Thing::from(("user", "tobie")));
+        round_trip(Value::from("raw"));
+        round_trip(Box::new(1i64));
+        round_trip(Some(1i64));
+        round_trip(None::<i64>);
+        round_trip(vec![1i64, 2]);
+        round_trip([1u8; 4]);
+        round_trip((1.0f64, 2.0f64));
+        round_trip(HashMap::from([("k".to_owned(), 1i32)]));
+        round_trip(BTreeMap::from([(1i32, "v".to_owned())]));
+        round_trip(HashSet::from([1u8, 2]));
+        round_trip(BTreeSet::from(['a', 'b']));
+    }
+
+    #[test]
+    fn test_chrono_types() {
+        let datetime = DateTime::parse_from_rfc3339("2024-01-31T10:30:00+07:00").unwrap();
+        round_trip(datetime.naive_utc());
+        round_trip(datetime.date_naive());
+        round_trip(datetime.time());
+        round_trip(datetime.with_timezone(&Local));
+        assert_eq!(
+            DateTime::<FixedOffset>::deserialize(&datetime.serialize()),
+            Ok(datetime.to_utc().fixed_offset())
+        );
+        assert_eq!(
+            DateTime::<FixedOffset>::deserialize(&Value::from("2024-01-31T10:30:00+07:00")),
+            Ok(datetime)
+        );
+        assert_eq!(
+            DateTime::<Utc>::deserialize(&Value::from("2024-01-31T10:30:00+07:00")),
+            Ok(datetime.to_utc())
+        );
+        assert_eq!(
+            NaiveDate::deserialize(&Value::from("2024-01-31")),
+            Ok(datetime.date_naive())
+        );
+        assert_eq!(
+            NaiveDate::deserialize(&datetime.date_naive().serialize()),
+            Ok(datetime.date_naive())
+        );
+    }
+
+    #[test]
+    fn test_bytes() {
+        let bytes = ByteBuf(vec![0, 1, 254, 255]);
+        assert_eq!(
+            bytes.clone().serialize(),
+            Value::Bytes(Bytes::from(bytes.to_vec()))
+        );
+        round_trip(bytes.clone());
+        assert_eq!(
+            ByteBuf::deserialize(&Value::from("AAH+/w==")),
+            Ok(bytes.clone())
+        );
+        assert_eq!(
+            ByteBuf::deserialize(&Value::from("AAH+/w")),
+            Ok(bytes.clone())
+        );
+
+        let boxed: Box<[u8]> = bytes.clone().into();
+        assert!(matches!(boxed.clone().serialize(), Value::Array(_)));
+        assert_eq!(ByteBuf::from(boxed).serialize(), bytes.serialize());
+        assert_eq!(
+            ByteBuf::deserialize(&Value::from("not base64!"))
+                .unwrap_err()
+                .kind(),
+            &SurrealResponseError::ExpectedABytes
+        );
+    }
+
+    #[test]
+    fn test_uuid_accepts_strings() {
+        let uuid = Uuid::from_u128(0x1234);
+        assert_eq!(uuid.serialize(), Value::Uuid(uuid.into()));
+        assert_eq!(Uuid::deserialize(&Value::from(uuid.to_string())), Ok(uuid));
+        assert_eq!(
+            Uuid::deserialize(&Value::from("nope")).unwrap_err().kind(),
+            &SurrealResponseError::ExpectedAUuid
+        );
+    }
+
+    #[test]
+    fn test_vec_error_records_index_and_field() {
+        let mut object = Object::default();
+        object.insert(
+            "tags".to_owned(),
+            Value::from(vec![Value::from("a"), Value::from(1)]),
+        );
+
+        let error = Vec::<String>::from_field(&object, "tags").unwrap_err();
+        assert_eq!(error.path().unwrap().to_string(), "tags[1]");
+        assert_eq!(error.kind(), &SurrealResponseError::ExpectedAStrand);
+    }
+
+    #[test]
+    fn test_checked_integer_conversions() {
+        assert_eq!(i32::deserialize(&Value::from(42)), Ok(42));
+        assert_eq!(u64::deserialize(&Value::from(7.0)), Ok(7));
+        assert_eq!(
+            i32::deserialize(&Value::from(i64::MAX)).unwrap_err().kind(),
+            &SurrealResponseError::NumberOverflow
+        );
+        assert_eq!(
+            u64::deserialize(&Value::from(-1)).unwrap_err().kind(),
+            &SurrealResponseError::NumberSignMismatch
+        );
+        assert_eq!(
+            u32::deserialize(&Value::from(1.5)).unwrap_err().kind(),
+            &SurrealResponseError::NumberPrecisionLoss
+        );
+        assert_eq!(u64::deserialize(&u64::MAX.serialize()), Ok(u64::MAX));
+    }
+
+    #[test]
+    fn test_wide_integers_round_trip() {
+        for value in [0, i64::MAX as i128 + 1, i128::MIN, i128::MAX] {
+            assert_eq!(i128::deserialize(&value.serialize()), Ok(value));
+        }
+        for value in [0, u64::MAX as u128, u128::MAX] {
+            assert_eq!(u128::deserialize(&value.serialize()), Ok(value));
+        }
+        assert_eq!(
+            u128::deserialize(&Value::from("-1")).unwrap_err().kind(),
+            &SurrealResponseError::NumberSignMismatch
+        );
+    }
+
+    #[test]
+    fn test_char_and_non_zero() {
+        assert_eq!(char::deserialize(&'x'.serialize()), Ok('x'));
+        assert_eq!(
+            char::deserialize(&Value::from("xy")).unwrap_err().kind(),
+            &SurrealResponseError::ExpectedAChar
+        );
+        assert_eq!(
+            NonZeroU16::deserialize(&Value::from(8080)),
+            Ok(NonZeroU16::new(8080).unwrap())
+        );
+        assert_eq!(
+            NonZeroU8::deserialize(&Value::from(0)).unwrap_err().kind(),
+            &SurrealResponseError::ExpectedANonZeroNumber
+        );
+    }
+
+    #[test]
+    fn test_pointers_and_sequences() {
+        round_trip(Arc::new(1u8));
+        round_trip(Rc::new(String::from("shared")));
+        round_trip(Cow::<'static, str>::Borrowed("borrowed"));
+        round_trip(VecDeque::from([1, 2, 3]));
+        round_trip(LinkedList::from([1, 2, 3]));
+        round_trip(vec![0u8, 255].into_boxed_slice());
+        assert_eq!(
+            vec![0u8, 255].into_boxed_slice().serialize(),
+            vec![0u8, 255].serialize()
+        );
+        assert_eq!("text".serialize(), Value::from("text"));
+        assert_eq!(Arc::<str>::from("text").serialize(), Value::from("text"));
+
+        let heap = BinaryHeap::from([3, 1, 2]);
+        assert_eq!(heap.serialize_ref(), vec![1, 2, 3].serialize());
+        assert_eq!(heap.clone().serialize(), vec![1, 2, 3].serialize());
+        assert_eq!(
+            BinaryHeap::<i32>::deserialize(&heap.serialize_ref())
+                .unwrap()
+                .into_sorted_vec(),
+            vec![1, 2, 3]
+        );
+
+        let error =
+            VecDeque::<u8>::deserialize(&Value::from(vec![Value::from(1), Value::from("x")]))
+                .unwrap_err();
+        assert_eq!(error.path().unwrap().to_string(), "[1]");
+    }
+
+    #[test]
+    fn test_result_and_either() {
+        round_trip(Ok::<i64, String>(1));
+        round_trip(Err::<i64, String>("failed".to_owned()));
+        assert_eq!(
+            Err::<i64, String>("failed".to_owned()).serialize(),
+            Value::Object(Object::from(BTreeMap::from([(
+                "err".to_owned(),
+                Value::from("failed")
+            )])))
+        );
+        assert_eq!(
+            Result::<i64, String>::deserialize(&Value::from(1))
+                .unwrap_err()
+                .kind(),
+            &SurrealResponseError::InvalidEnumFormat
+        );
+
+        round_trip(Either::<i64, String>::Left(1));
+        round_trip(Either::<i64, String>::Right("text".to_owned()));
+        let error = Either::<i64, String>::deserialize(&Value::Bool(true)).unwrap_err();
+        match error.kind() {
+            SurrealResponseError::NoMatchingVariant(errors) => {
+                assert_eq!(errors[0].kind(), &SurrealResponseError::ExpectedANumberI64);
+                assert_eq!(errors[1].kind(), &SurrealResponseError::ExpectedAStrand);
+            }
+            _ => panic!("Expected both variants to fail"),
+        }
+    }
+
+    #[test]
+    fn test_network_types_and_paths() {
+        round_trip(IpAddr::from([127, 0, 0, 1]));
+        round_trip(Ipv6Addr::LOCALHOST);
+        round_trip(SocketAddr::from(([10, 0, 0, 1], 8000)));
+        round_trip(PathBuf::from("/var/log/audit.log"));
+        assert_eq!(
+            SocketAddr::from(([10, 0, 0, 1], 8000)).serialize(),
+            Value::from("10.0.0.1:8000")
+        );
+
+        let error = IpAddr::deserialize(&Value::from("localhost")).unwrap_err();
+        assert!(matches!(error.kind(), SurrealResponseError::ParseError(_)));
+        assert_eq!(
+            Ipv4Addr::deserialize(&Value::from(1)).unwrap_err().kind(),
+            &SurrealResponseError::ExpectedAStrand
+        );
+    }
+
+    #[test]
+    fn test_maps_and_sets() {
+        let map = HashMap::from([("name".to_owned(), 1u8), ("age".to_owned(), 2u8)]);
+        assert_eq!(HashMap::deserialize(&map.clone().serialize()), Ok(map));
+
+        let map = BTreeMap::from([(1u16, 'a'), (2u16, 'b')]);
+        assert_eq!(BTreeMap::deserialize(&map.clone().serialize()), Ok(map));
+
+        let set = BTreeSet::from(["a".to_owned(), "b".to_owned()]);
+        assert_eq!(BTreeSet::deserialize(&set.clone().serialize()), Ok(set));
+
+        let error = HashSet::<String>::deserialize(&Value::from(vec!["a", "b", "a"])).unwrap_err();
+        assert_eq!(error.kind(), &SurrealResponseError::ExpectedASet);
+        assert_eq!(error.path().unwrap().to_string(), "[2]");
+    }
+
+    #[test]
+    fn test_tuples_and_arrays() {
+        let tuple = ("a".to_owned(), 1i64, true);
+        assert_eq!(
+            <(String, i64, bool)>::deserialize(&tuple.clone().serialize()),
+            Ok(tuple)
+        );
+
+        let array = [1u8, 2, 3];
+        assert_eq!(<[u8; 3]>::deserialize(&array.serialize()), Ok(array));
+
+        let error = <(f64, f64)>::deserialize(&Value::from(vec![1.0f64])).unwrap_err();
+        assert_eq!(
+            error.kind(),
+            &SurrealResponseError::ArrayLengthMismatch {
+                expected: 2,
+                found: 1
+            }
+        );
+    }
+
+    #[test]
+    fn test_saturating_integer_conversions() {
+        assert_eq!(
+            Saturating::<i32>::deserialize(&Value::from(i64::MAX)),
+            Ok(Saturating(i32::MAX))
+        );
+        assert_eq!(
+            Saturating::<u64>::deserialize(&Value::from(-1)),
+            Ok(Saturating(0))
+        );
+        assert_eq!(
+            Saturating::<u32>::deserialize(&Value::from(1.9)),
+            Ok(Saturating(1))
+        );
+        assert_eq!(
+            Saturating::<u64>::deserialize(&u128::MAX.serialize()),
+            Ok(Saturating(u64::MAX))
+        );
+        assert_eq!(
+            Saturating::<u128>::deserialize(&i128::MIN.serialize()),
+            Ok(Saturating(0))
+        );
+        assert_eq!(
+            Saturating::<i128>::deserialize(&Value::from("-1".repeat(50)))
+                .unwrap_err()
+                .kind(),
+            &SurrealResponseError::ExpectedANumberI64
+        );
+        assert_eq!(
+            Saturating::<i128>::deserialize(&Value::from("9".repeat(50))),
+            Ok(Saturating(i128::MAX))
+        );
+    }
+}
@@ -0,0 +1,487 @@
+//! Runtime used by the derive to store enums, following the tagging strategies of serde.
+//!
+//! Unit variants are always stored as a plain string in the externally tagged representation,
+//! and a plain string is accepted as a unit variant in every representation.
+use std::collections::BTreeMap;
+
+use surrealdb::sql::{Object, Value};
+
+use crate::proxy::default::SurrealDeserializer;
+use crate::surreal_qr::SurrealResponseError;
+
+/// How the variant of an enum is recorded in the stored value.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum EnumRepr {
+    /// `{ "Variant": content }`, or `"Variant"` for unit variants.
+    External,
+    /// `{ "type": "Variant", ..fields }` for unit, struct and newtype-of-object variants.
+    ///
+    /// The content of the other variants is not an object, so it is stored under the reserved
+    /// `INTERNAL_CONTENT` field: `{ "type": "Variant", "_content": content }`. An object that has
+    /// an `INTERNAL_CONTENT` field of its own is stored the same way, so that it is not mistaken
+    /// for wrapped content when read back.
+    Internal { tag: &'static str },
+    /// `{ "type": "Variant", "content": content }`, or `{ "type": "Variant" }` for unit variants.
+    Adjacent {
+        tag: &'static str,
+        content: &'static str,
+    },
+}
+
+/// The payload of a variant, already serialized.
+#[derive(Debug, Clone, PartialEq)]
+pub enum VariantContent {
+    Unit,
+    Newtype(Value),
+    Tuple(Vec<Value>),
+    Struct(Vec<(String, Value)>),
+}
+
+impl VariantContent {
+    fn into_value(self) -> Option<Value> {
+        match self {
+            Self::Unit => None,
+            Self::Newtype(value) => Some(value),
+            Self::Tuple(items) => Some(Value::from(items)),
+            Self::Struct(fields) => Some(Value::Object(Object::from(
+                fields.into_iter().collect::<BTreeMap<_, _>>(),
+            ))),
+        }
+    }
+}
+
+impl EnumRepr {
+    /// Field holding the content of an internally tagged variant that is not an object.
+    pub const INTERNAL_CONTENT: &'static str = "_content";
+
+    /// Store `variant` with its `content`.
+    pub fn serialize(self, variant: &str, content: VariantContent) -> Value {
+        match self {
+            Self::External => match content.into_value() {
+                None => Value::from(variant),
+                Some(content) => object([(variant.to_owned(), content)]),
+            },
+            Self::Internal { tag } => match content.into_value() {
+                None => object([(tag.to_owned(), Value::from(variant))]),
+                Some(Value::Object(mut fields)) if !fields.contains_key(Self::INTERNAL_CONTENT) => {
+                    fields.insert(tag.to_owned(), Value::from(variant));
+                    Value::Object(fields)
+                }
+                Some(content) => object([
+                    (tag.to_owned(), Value::from(variant)),
+                    (Self::INTERNAL_CONTENT.to_owned(), content),
+                ]),
+            },
+            Self::Adjacent {
+                tag,
+                content: content_field,
+            } => match content.into_value() {
+                None => object([(tag.to_owned(), Value::from(variant))]),
+                Some(content) => object([
+                    (tag.to_owned(), Value::from(variant)),
+                    (content_field.to_owned(), content),
+                ]),
+            },
+        }
+    }
+
+    /// Read the variant of the enum `E` stored in `value`.
+    pub fn deserialize<E>(self, value: &Value) -> Result<Tagged<'_>, SurrealResponseError> {
+        let invalid = || SurrealResponseError::InvalidEnumFormat.mismatch::<E>(value);
+
+        let object = match value {
+            Value::Strand(variant) => {
+                return Ok(Tagged {
+                    variant: variant.as_str(),
+                    content: None,
+                    content_field: None,
+                })
+            }
+            Value::Object(object) => object,
+            _ => return Err(invalid()),
+        };
+
+        match self {
+            Self::External => {
+                let mut entries = object.iter();
+                match (entries.next(), entries.next()) {
+                    (Some((variant, content)), None) => Ok(Tagged {
+                        variant: variant.as_str(),
+                        content: Some(content),
+                        content_field: Some(variant.as_str()),
+                    }),
+                    _ => Err(invalid()),
+                }
+            }
+            Self::Internal { tag } => {
+                let variant = tag_of::<E>(object, tag, value)?;
+                match object.get(Self::INTERNAL_CONTENT) {
+                    Some(content) if object.len() == 2 => Ok(Tagged {
+                        variant,
+                        content: Some(content),
+                        content_field: Some(Self::INTERNAL_CONTENT),
+                    }),
+                    _ => Ok(Tagged {
+                        variant,
+                        content: Some(value),
+                        content_field: None,
+                    }),
+                }
+            }
+            Self::Adjacent {
+                tag,
+                content: content_field,
+            } => Ok(Tagged {
+                variant: tag_of::<E>(object, tag, value)?,
+                content: object.get(content_field),
+                content_field: Some(content_field),
+            }),
+        }
+    }
+}
+
+fn object<const N: usize>(entries: [(String, Value); N]) -> Value {
+    Value::Object(Object::from(BTreeMap::from(entries)))
+}
+
+fn tag_of<'a, E>(
+    object: &'a Object,
+    tag: &str,
+    value: &Value,
+) -> Result<&'a str, SurrealResponseError> {
+    match object.get(tag) {
+        Some(Value::Strand(variant)) => Ok(variant.as_str()),
+        Some(found) => Err(SurrealResponseError::TypeEnumMustBeString
+            .mismatch::<E>(found)
+            .at_field(tag)),
+        None => Err(SurrealResponseError::InvalidEnumFormat.mismatch::<E>(value)),
+    }
+}
+
+/// A stored variant, whose content is read according to the shape of the variant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct Tagged<'a> {
+    /// Name of the variant, as stored.
+    pub variant: &'a str,
+    content: Option<&'a Value>,
+    /// Field that holds the content, recorded in the error path.
+    content_field: Option<&'a str>,
+}
+
+impl<'a> Tagged<'a> {
+    /// The error for a variant name that the enum `E` does not have.
+    pub fn unknown_variant<E>(&self) -> SurrealResponseError {
+        SurrealResponseError::UnknownVariant.mismatch::<E>(&Value::from(self.variant))
+    }
+
+    /// Read the content of a newtype variant.
+    pub fn newtype<T>(&self) -> Result<T, SurrealResponseError>
+    where
+        T: SurrealDeserializer,
+    {
+        T::from_option(self.content).map_err(|e| self.at_content(e))
+    }
+
+    /// Read the content of a tuple variant, which must have exactly `len` items.
+    pub fn tuple(&self, len: usize) -> Result<TaggedItems<'a>, SurrealResponseError> {
+        match self.content {
+            Some(Value::Array(array)) if array.len() == len => Ok(TaggedItems {
+                items: &array.0,
+                tagged: *self,
+            }),
+            Some(content @ Value::Array(_)) => Err(self.at_content(
+                SurrealResponseError::NumberOfFieldOfLengthOfDbValueNotMatchLengthOfEnum
+                    .mismatch::<Vec<Value>>(content),
+            )),
+            content => Err(self.at_content(
+                SurrealResponseError::ExpectedAnArray
+                    .mismatch::<Vec<Value>>(content.unwrap_or(&Value::None)),
+            )),
+        }
+    }
+
+    /// Read the field `field` of a struct variant.
+    pub fn field<T>(&self, field: &str) -> Result<T, SurrealResponseError>
+    where
+        T: SurrealDeserializer,
+    {
+        match self.content {
+            Some(Value::Object(object)) => T::from_field(object, field),
+            content => Err(SurrealResponseError::ExpectedAnObject
+                .mismatch::<Object>(content.unwrap_or(&Value::None))),
+        }
+        .map_err(|e| self.at_content(e))
+    }
+
+    fn at_content(&self, error: SurrealResponseError) -> SurrealResponseError {
+        match self.content_field {
+            Some(field) => error.at_field(field),
+            None => error,
+        }
+    }
+}
+
+/// The items of a tuple variant.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct TaggedItems<'a> {
+    items: &'a [Value],
+    tagged: Tagged<'a>,
+}
+
+impl TaggedItems<'_> {
+    /// Read the item at `index`.
+    pub fn item<T>(&self, index: usize) -> Result<T, SurrealResponseError>
+    where
+        T: SurrealDeserializer,
+    {
+        let Some(item) = self.items.get(index) else {
+            return Err(self.tagged.at_content(
+                SurrealResponseError::NumberOfFieldOfLengthOfDbValueNotMatchLengthOfEnum
+                    .mismatch::<Vec<Value>>(&Value::from(self.items.to_vec())),
+            ));
+        };
+
+        T::deserialize(item).map_err(|e| self.tagged.at_content(e.at_index(index)))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::proxy::default::{SurrealSerializer, SurrealSerializerRef};
+
+    #[derive(Debug, Clone, PartialEq)]
+    enum Shape {
+        Empty,
+        Circle(f64),
+        Line(i64, i64),
+        Rect { width: i64, height: i64 },
+    }
+
+    impl Shape {
+        fn serialize(self, repr: EnumRepr) -> Value {
+            match self {
+                Shape::Empty => repr.serialize("Empty", VariantContent::Unit),
+                Shape::Circle(radius) => {
+                    repr.serialize("Circle", VariantContent::Newtype(radius.serialize()))
+                }
+                Shape::Line(from, to) => repr.serialize(
+                    "Line",
+                    VariantContent::Tuple(vec![from.serialize(), to.serialize()]),
+                ),
+                Shape::Rect { width, height } => repr.serialize(
+                    "Rect",
+                    VariantContent::Struct(vec![
+                        ("width".to_owned(), width.serialize()),
+                        ("height".to_owned(), height.serialize()),
+                    ]),
+                ),
+            }
+        }
+
+        fn deserialize(repr: EnumRepr, value: &Value) -> Result<Shape, SurrealResponseError> {
+            let tagged = repr.deserialize::<Shape>(value)?;
+            match tagged.variant {
+                "Empty" => Ok(Shape::Empty),
+                "Circle" => Ok(Shape::Circle(tagged.newtype()?)),
+                "Line" => {
+                    let items = tagged.tuple(2)?;
+                    Ok(Shape::Line(items.item(0)?, items.item(1)?))
+                }
+                "Rect" => Ok(Shape::Rect {
+                    width: tagged.field("width")?,
+                    height: tagged.field("height")?,
+                }),
+                _ => Err(tagged.unknown_variant::<Shape>()),
+            }
+        }
+    }
+
+    fn round_trip(repr: EnumRepr, shapes: Vec<Shape>) {
+        for shape in shapes {
+            let value = shape.clone().serialize(repr);
+            assert_eq!(Shape::deserialize(repr, &value), Ok(shape));
+        }
+    }
+
+    fn all_shapes() -> Vec<Shape> {
+        vec![
+            Shape::Empty,
+            Shape::Circle(1.5),
+            Shape::Line(1, 2),
+            Shape::Rect {
+                width: 3,
+                height: 4,
+            },
+        ]
+    }
+
+    #[test]
+    fn test_externally_tagged() {
+        let repr = EnumRepr::External;
+        round_trip(repr, all_shapes());
+        assert_eq!(Shape::Empty.serialize(repr), Value::from("Empty"));
+        assert_eq!(
+            Shape::Circle(1.5).serialize(repr),
+            object([("Circle".to_owned(), Value::from(1.5))])
+        );
+
+        let error = Shape::deserialize(repr, &object([("Line".to_owned(), vec![1].serialize())]))
+            .unwrap_err();
+        assert_eq!(
+            error.kind(),
+            &SurrealResponseError::NumberOfFieldOfLengthOfDbValueNotMatchLengthOfEnum
+        );
+        assert_eq!(error.path().unwrap().to_string(), "Line");
+    }
+
+    #[test]
+    fn test_internally_tagged() {
+        let repr = EnumRepr::Internal { tag: "type" };
+        round_trip(
+            repr,
+            vec![
+                Shape::Empty,
+                Shape::Rect {
+                    width: 3,
+                    height: 4,
+                },
+            ],
+        );
+        assert_eq!(
+            Shape::Empty.serialize(repr),
+            object([("type".to_owned(), Value::from("Empty"))])
+        );
+
+        let error = Shape::deserialize(
+            repr,
+            &object([
+                ("type".to_owned(), Value::from("Rect")),
+                ("width".to_owned(), Value::from("wide")),
+            ]),
+        )
+        .unwrap_err();
+        assert_eq!(error.path().unwrap().to_string(), "width");
+
+        let error =
+            Shape::deserialize(repr, &object([("type".to_owned(), Value::from(1))])).unwrap_err();
+        assert_eq!(error.kind(), &SurrealResponseError::TypeEnumMustBeString);
+    }
+
+    #[test]
+    fn test_internally_tagged_non_object_content() {
+        let repr = EnumRepr::Internal { tag: "type" };
+        round_trip(repr, all_shapes());
+        assert_eq!(
+            Shape::Line(1, 2).serialize(repr),
+            object([
+                ("type".to_owned(), Value::from("Line")),
+                ("_content".to_owned(), vec![1, 2].serialize_ref()),
+            ])
+        );
+
+        let error = Shape::deserialize(
+            repr,
+            &object([
+                ("type".to_owned(), Value::from("Circle")),
+                ("_content".to_owned(), Value::from("wide")),
+            ]),
+        )
+        .unwrap_err();
+        assert_eq!(error.path().unwrap().to_string(), "_content");
+    }
+
+    #[test]
+    fn test_internally_tagged_content_field_collision() {
+        let repr = EnumRepr::Internal { tag: "type" };
+        for fields in [
+            vec![("_content".to_owned(), Value::from(1))],
+            vec![
+                ("_content".to_owned(), Value::from(1)),
+                ("other".to_owned(), Value::from(2)),
+            ],
+        ] {
+            let value = repr.serialize("Wrapper", VariantContent::Struct(fields.clone()));
+            let tagged = repr.deserialize::<Shape>(&value).unwrap();
+            assert_eq!(tagged.field::<i64>("_content"), Ok(1));
+            assert_eq!(
+                tagged.newtype::<Value>(),
+                Ok(Value::Object(Object::from(
+                    fields.into_iter().collect::<BTreeMap<_, _>>()
+                )))
+            );
+        }
+    }
+
+    #[test]
+    fn test_tuple_item_out_of_range() {
+        let repr = EnumRepr::External;
+        let value = Shape::Line(1, 2).serialize(repr);
+        let items = repr.deserialize::<Shape>(&value).unwrap().tuple(2).unwrap();
+
+        let error = items.item::<i64>(2).unwrap_err();
+        assert_eq!(
+            error.kind(),
+            &SurrealResponseError::NumberOfFieldOfLengthOfDbValueNotMatchLengthOfEnum
+        );
+        assert_eq!(error.path().unwrap().to_string(), "Line");
+    }
+
+    #[test]
+    fn test_adjacently_tagged() {
+        let repr = EnumRepr::Adjacent {
+            tag: "t",
+            content: "c",
+        };
+        round_trip(repr, all_shapes());
+        assert_eq!(
+            Shape::Line(1, 2).serialize(repr),
+            object([
+                ("t".to_owned(), Value::from("Line")),
+                ("c".to_owned(), vec![1, 2].serialize_ref()),
+            ])
+        );
+
+        let error = Shape::deserialize(
+            repr,
+            &object([
+                ("t".to_owned(), Value::from("Line")),
+                (
+                    "c".to_owned(),
+                    Value::from(vec![Value::from(1), Value::from("2")]),
+                ),
+            ]),
+        )
+        .unwrap_err();
+        assert_eq!(error.path().unwrap().to_string(), "c[1]");
+    }
+
+    #[test]
+    fn test_unit_as_string_and_unknown_variants() {
+        for repr in [
+            EnumRepr::External,
+            EnumRepr::Internal { tag: "type" },
+            EnumRepr::Adjacent {
+                tag: "t",
+                content: "c",
+            },
+        ] {
+            assert_eq!(
+                Shape::deserialize(repr, &Value::from("Empty")),
+                Ok(Shape::Empty)
+            );
+            assert_eq!(
+                Shape::deserialize(repr, &Value::from("Hexagon"))
+                    .unwrap_err()
+                    .kind(),
+                &SurrealResponseError::UnknownVariant
+            );
+            assert_eq!(
+                Shape::deserialize(repr, &Value::from(1))
+                    .unwrap_err()
+                    .kind(),
+                &SurrealResponseError::InvalidEnumFormat
+            );
+        }
+    }
+}
@@ -0,0 +1,168 @@
+use geo_types::{
+    Geometry as GeoGeometry, GeometryCollection, LineString, MultiLineString, MultiPoint,
+    MultiPolygon, Point, Polygon,
+};
+use surrealdb::sql::{Geometry, Value};
+
+use crate::proxy::default::{SurrealDeserializer, SurrealSerializer, SurrealSerializerRef};
+use crate::surreal_qr::SurrealResponseError;
+
+/// Lines, rects and triangles have no SurrealDB counterpart, they are stored as a line string
+/// and polygons.
+fn to_sql(geometry: GeoGeometry<f64>) -> Geometry {
+    match geometry {
+        GeoGeometry::Point(v) => Geometry::Point(v),
+        GeoGeometry::Line(v) => Geometry::Line(v.into()),
+        GeoGeometry::LineString(v) => Geometry::Line(v),
+        GeoGeometry::Polygon(v) => Geometry::Polygon(v),
+        GeoGeometry::MultiPoint(v) => Geometry::MultiPoint(v),
+        GeoGeometry::MultiLineString(v) => Geometry::MultiLine(v),
+        GeoGeometry::MultiPolygon(v) => Geometry::MultiPolygon(v),
+        GeoGeometry::GeometryCollection(v) => {
+            Geometry::Collection(v.into_iter().map(to_sql).collect())
+        }
+        GeoGeometry::Rect(v) => Geometry::Polygon(v.to_polygon()),
+        GeoGeometry::Triangle(v) => Geometry::Polygon(v.to_polygon()),
+    }
+}
+
+fn from_sql<T>(
+    geometry: &Geometry,
+    value: &Value,
+) -> Result<GeoGeometry<f64>, SurrealResponseError> {
+    Ok(match geometry {
+        Geometry::Point(v) => GeoGeometry::Point(*v),
+        Geometry::Line(v) => GeoGeometry::LineString(v.clone()),
+        Geometry::Polygon(v) => GeoGeometry::Polygon(v.clone()),
+        Geometry::MultiPoint(v) => GeoGeometry::MultiPoint(v.clone()),
+        Geometry::MultiLine(v) => GeoGeometry::MultiLineString(v.clone()),
+        Geometry::MultiPolygon(v) => GeoGeometry::MultiPolygon(v.clone()),
+        Geometry::Collection(v) => GeoGeometry::GeometryCollection(
+            v.iter()
+                .enumerate()
+                .map(|(index, item)| from_sql::<T>(item, value).map_err(|e| e.at_index(index)))
+                .collect::<Result<_, _>>()?,
+        ),
+        _ => return Err(SurrealResponseError::ExpectedAGeometry.mismatch::<T>(value)),
+    })
+}
+
+impl SurrealSerializer for GeoGeometry<f64> {
+    fn serialize(self) -> Value {
+        Value::Geometry(to_sql(self))
+    }
+}
+
+impl SurrealSerializerRef for GeoGeometry<f64> {
+    fn serialize_ref(&self) -> Value {
+        self.clone().serialize()
+    }
+}
+
+impl SurrealDeserializer for GeoGeometry<f64> {
+    fn deserialize(value: &Value) -> Result<Self, SurrealResponseError> {
+        match value {
+            Value::Geometry(geometry) => from_sql::<Self>(geometry, value),
+            _ => Err(SurrealResponseError::ExpectedAGeometry.mismatch::<Self>(value)),
+        }
+    }
+}
+
+macro_rules! geometry_impls {
+    ($($ty:ty => $variant:ident),*) => {
+        $(
+            impl SurrealSerializer for $ty {
+                fn serialize(self) -> Value {
+                    Value::Geometry(Geometry::$variant(self))
+                }
+            }
+
+            impl SurrealSerializerRef for $ty {
+                fn serialize_ref(&self) -> Value {
+                    self.clone().serialize()
+                }
+            }
+
+            impl SurrealDeserializer for $ty {
+                fn deserialize(value: &Value) -> Result<Self, SurrealResponseError> {
+                    match value {
+                        Value::Geometry(Geometry::$variant(v)) => Ok(v.clone()),
+                        _ => Err(SurrealResponseError::ExpectedAGeometry.mismatch::<Self>(value)),
+                    }
+                }
+            }
+        )*
+    };
+}
+
+geometry_impls!(
+    Point<f64> => Point,
+    LineString<f64> => Line,
+    Polygon<f64> => Polygon,
+    MultiPoint<f64> => MultiPoint,
+    MultiLineString<f64> => MultiLine,
+    MultiPolygon<f64> => MultiPolygon
+);
+
+impl SurrealSerializer for GeometryCollection<f64> {
+    fn serialize(self) -> Value {
+        GeoGeometry::GeometryCollection(self).serialize()
+    }
+}
+
+impl SurrealSerializerRef for GeometryCollection<f64> {
+    fn serialize_ref(&self) -> Value {
+        self.clone().serialize()
+    }
+}
+
+impl SurrealDeserializer for GeometryCollection<f64> {
+    fn deserialize(value: &Value) -> Result<Self, SurrealResponseError> {
+        match value {
+            Value::Geometry(geometry @ Geometry::Collection(_)) => {
+                match from_sql::<Self>(geometry, value)? {
+                    GeoGeometry::GeometryCollection(collection) => Ok(collection),
+                    _ => unreachable!("A collection always converts to a collection"),
+                }
+            }
+            _ => Err(SurrealResponseError::ExpectedAGeometry.mismatch::<Self>(value)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use geo_types::{line_string, point, polygon};
+
+    #[test]
+    fn test_round_trip() {
+        let point = point!(x: 1.0, y: 2.0);
+        assert_eq!(Point::deserialize(&point.serialize()), Ok(point));
+
+        let line = line_string![(x: 0.0, y: 0.0), (x: 1.0, y: 1.0)];
+        assert_eq!(
+            LineString::deserialize(&line.clone().serialize()),
+            Ok(line.clone())
+        );
+
+        let polygon = polygon![(x: 0.0, y: 0.0), (x: 1.0, y: 0.0), (x: 1.0, y: 1.0)];
+        let collection = GeometryCollection::from(vec![
+            GeoGeometry::Point(point),
+            GeoGeometry::LineString(line),
+            GeoGeometry::Polygon(polygon),
+        ]);
+        assert_eq!(
+            GeometryCollection::deserialize(&collection.clone().serialize()),
+            Ok(collection)
+        );
+    }
+
+    #[test]
+    fn test_reports_the_found_geometry() {
+        let error =
+            Point::<f64>::deserialize(&LineString::from(vec![(0.0, 0.0)]).serialize()).unwrap_err();
+        assert_eq!(error.kind(), &SurrealResponseError::ExpectedAGeometry);
+        assert!(error.to_string().ends_with("found geometry<line>"));
+    }
+}
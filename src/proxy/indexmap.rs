@@ -0,0 +1,178 @@
+//! `IndexMap` is stored as an array of `[key, value]` pairs, since SurrealDB keeps the keys of an
+//! object sorted, and an object is still read back in key order. `IndexSet` is stored as an
+//! array. Both keep their insertion order in the database.
+use std::hash::{BuildHasher, Hash};
+
+use indexmap::{IndexMap, IndexSet};
+use surrealdb::sql::Value;
+
+use crate::proxy::default::{
+    deserialize_map, deserialize_set, ObjectKey, SurrealDeserializer, SurrealSerializer,
+    SurrealSerializerRef,
+};
+use crate::surreal_qr::SurrealResponseError;
+
+impl<K, V, S> SurrealSerializer for IndexMap<K, V, S>
+where
+    K: ObjectKey,
+    V: SurrealSerializer,
+{
+    fn serialize(self) -> Value {
+        Value::Array(
+            self.into_iter()
+                .map(|(key, value)| entry(&key, value.serialize()))
+                .collect(),
+        )
+    }
+}
+
+impl<K, V, S> SurrealSerializerRef for IndexMap<K, V, S>
+where
+    K: ObjectKey,
+    V: SurrealSerializerRef,
+{
+    fn serialize_ref(&self) -> Value {
+        Value::Array(
+            self.iter()
+                .map(|(key, value)| entry(key, value.serialize_ref()))
+                .collect(),
+        )
+    }
+}
+
+impl<K, V, S> SurrealDeserializer for IndexMap<K, V, S>
+where
+    K: ObjectKey + Eq + Hash,
+    V: SurrealDeserializer,
+    S: BuildHasher + Default,
+{
+    fn deserialize(value: &Value) -> Result<Self, SurrealResponseError> {
+        let array = match value {
+            Value::Object(_) => return deserialize_map(value),
+            Value::Array(array) => array,
+            _ => return Err(SurrealResponseError::ExpectedAnArray.mismatch::<Self>(value)),
+        };
+
+        let mut map = Self::default();
+        for (index, item) in array.iter().enumerate() {
+            let (key, item) = read_entry(item).map_err(|e| e.at_index(index))?;
+            if map.insert(key, item).is_some() {
+                return Err(SurrealResponseError::ExpectedASet
+                    .mismatch::<Self>(value)
+                    .at_index(index));
+            }
+        }
+
+        Ok(map)
+    }
+}
+
+fn entry<K: ObjectKey>(key: &K, value: Value) -> Value {
+    Value::from(vec![Value::from(key.to_key()), value])
+}
+
+fn read_entry<K, V>(entry: &Value) -> Result<(K, V), SurrealResponseError>
+where
+    K: ObjectKey,
+    V: SurrealDeserializer,
+{
+    let pair = match entry {
+        Value::Array(array) => array.as_slice(),
+        _ => return Err(SurrealResponseError::ExpectedAnArray.mismatch::<(K, V)>(entry)),
+    };
+
+    let [key, value] = pair else {
+        return Err(SurrealResponseError::ArrayLengthMismatch {
+            expected: 2,
+            found: pair.len(),
+        }
+        .mismatch::<(K, V)>(entry));
+    };
+
+    let Value::Strand(key) = key else {
+        return Err(SurrealResponseError::ExpectedAStrand
+            .mismatch::<K>(key)
+            .at_index(0));
+    };
+
+    let key = K::from_key(key.as_str()).map_err(|e| e.at_index(0))?;
+    let value = V::deserialize(value).map_err(|e| e.at_index(1))?;
+    Ok((key, value))
+}
+
+impl<T, S> SurrealSerializer for IndexSet<T, S>
+where
+    T: SurrealSerializer,
+{
+    fn serialize(self) -> Value {
+        Value::Array(self.into_iter().map(|item| item.serialize()).collect())
+    }
+}
+
+impl<T, S> SurrealSerializerRef for IndexSet<T, S>
+where
+    T: SurrealSerializerRef,
+{
+    fn serialize_ref(&self) -> Value {
+        Value::Array(self.iter().map(|item| item.serialize_ref()).collect())
+    }
+}
+
+impl<T, S> SurrealDeserializer for IndexSet<T, S>
+where
+    T: SurrealDeserializer + Eq + Hash,
+    S: BuildHasher + Default,
+{
+    fn deserialize(value: &Value) -> Result<Self, SurrealResponseError> {
+        deserialize_set(value, |set: &mut Self, item| set.insert(item))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_index_map_keeps_order() {
+        let map = IndexMap::<String, i64>::from([("b".to_owned(), 1), ("a".to_owned(), 2)]);
+        let value = map.clone().serialize();
+        assert_eq!(
+            value,
+            Value::from(vec![
+                Value::from(vec![Value::from("b"), Value::from(1)]),
+                Value::from(vec![Value::from("a"), Value::from(2)]),
+            ])
+        );
+        assert_eq!(value, map.serialize_ref());
+
+        let read = IndexMap::<String, i64>::deserialize(&value).unwrap();
+        assert_eq!(read.keys().collect::<Vec<_>>(), vec!["b", "a"]);
+
+        let object = std::collections::BTreeMap::from([("b".to_owned(), 1)]).serialize();
+        assert_eq!(
+            IndexMap::<String, i64>::deserialize(&object),
+            Ok(IndexMap::from([("b".to_owned(), 1)]))
+        );
+
+        let error = IndexMap::<String, i64>::deserialize(&Value::from(vec![Value::from(vec![
+            Value::from("a"),
+        ])]))
+        .unwrap_err();
+        assert_eq!(
+            error.kind(),
+            &SurrealResponseError::ArrayLengthMismatch {
+                expected: 2,
+                found: 1
+            }
+        );
+        assert_eq!(error.path().unwrap().to_string(), "[0]");
+    }
+
+    #[test]
+    fn test_index_set_keeps_order() {
+        let set = IndexSet::<String>::from(["b".to_owned(), "a".to_owned()]);
+        let value = set.clone().serialize();
+        assert_eq!(value, Value::from(vec!["b", "a"]));
+        assert_eq!(IndexSet::deserialize(&value), Ok(set));
+    }
+}
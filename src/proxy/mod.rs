@@ -1 +1,19 @@
+#[cfg(feature = "bigdecimal")]
+mod bigdecimal;
+#[cfg(feature = "bytes")]
+mod bytes;
 pub mod default;
+pub mod enum_repr;
+#[cfg(feature = "geo")]
+mod geo;
+#[cfg(feature = "indexmap")]
+mod indexmap;
+#[cfg(feature = "rust_decimal")]
+mod rust_decimal;
+pub mod serde_bridge;
+#[cfg(feature = "serde_json")]
+mod serde_json;
+#[cfg(feature = "time")]
+mod time;
+#[cfg(feature = "url")]
+mod url;
@@ -0,0 +1,74 @@
+use std::str::FromStr;
+
+use rust_decimal::prelude::ToPrimitive;
+use rust_decimal::Decimal;
+use surrealdb::sql::{Number, Value};
+
+use crate::proxy::default::{
+    copy_serializer_ref_impls, SurrealDeserializer, SurrealSerializer, SurrealSerializerRef,
+};
+use crate::surreal_qr::SurrealResponseError;
+
+/// Convert a float through its shortest decimal representation, failing if the decimal cannot
+/// hold it exactly.
+fn decimal_from_float<T>(float: f64, value: &Value) -> Result<Decimal, SurrealResponseError> {
+    if !float.is_finite() {
+        return Err(SurrealResponseError::ExpectedANumberDecimal.mismatch::<T>(value));
+    }
+
+    match Decimal::from_str(&float.to_string()) {
+        Ok(decimal) if decimal.to_f64() == Some(float) => Ok(decimal),
+        Err(_) if float.abs() >= 1.0 => {
+            Err(SurrealResponseError::NumberOverflow.mismatch::<T>(value))
+        }
+        _ => Err(SurrealResponseError::NumberPrecisionLoss.mismatch::<T>(value)),
+    }
+}
+
+impl SurrealSerializer for Decimal {
+    fn serialize(self) -> Value {
+        Value::Number(Number::Decimal(self))
+    }
+}
+
+/// Reads decimals, ints, floats that a decimal holds exactly and decimal strings such as `"12.34"`.
+impl SurrealDeserializer for Decimal {
+    fn deserialize(value: &Value) -> Result<Decimal, SurrealResponseError> {
+        match value {
+            Value::Number(Number::Decimal(decimal)) => Ok(*decimal),
+            Value::Number(Number::Int(int)) => Ok(Decimal::from(*int)),
+            Value::Number(Number::Float(float)) => decimal_from_float::<Self>(*float, value),
+            Value::Strand(strand) => Decimal::from_str(strand.as_str())
+                .map_err(|_| SurrealResponseError::ExpectedANumberDecimal.mismatch::<Self>(value)),
+            _ => Err(SurrealResponseError::ExpectedANumberDecimal.mismatch::<Self>(value)),
+        }
+    }
+}
+
+copy_serializer_ref_impls!(Decimal);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn read(value: &Value) -> Result<Decimal, SurrealResponseError> {
+        <Decimal as SurrealDeserializer>::deserialize(value)
+    }
+
+    #[test]
+    fn test_decimal() {
+        let decimal = Decimal::from_str("12.34").unwrap();
+        assert_eq!(read(&decimal.serialize_ref()), Ok(decimal));
+        assert_eq!(read(&Value::from(12.34)), Ok(decimal));
+        assert_eq!(read(&Value::from("12.34")), Ok(decimal));
+        assert_eq!(read(&Value::from(7)), Ok(Decimal::from(7)));
+        assert_eq!(
+            read(&Value::from(1e-30)).unwrap_err().kind(),
+            &SurrealResponseError::NumberPrecisionLoss
+        );
+        assert_eq!(
+            read(&Value::from(1e30)).unwrap_err().kind(),
+            &SurrealResponseError::NumberOverflow
+        );
+    }
+}
@@ -0,0 +1,249 @@
+use std::collections::BTreeMap;
+use std::ops::{Deref, DerefMut};
+
+use serde::de::DeserializeOwned;
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::{Object, Value};
+
+use crate::config::SurrealDeriveConfig;
+use crate::naming_convention::{camel_to_snake_case, snake_case_to_camel};
+use crate::proxy::default::{SurrealDeserializer, SurrealSerializer, SurrealSerializerRef};
+use crate::surreal_qr::SurrealResponseError;
+
+/// Convert any serde type into a value.
+///
+/// The keys are the ones serde produces and `SurrealDeriveConfig::use_camel_case` is not
+/// applied, so a type is renamed through its own attributes, e.g.
+/// `#[serde(rename_all = "camelCase")]`. Map keys are never renamed. Use `to_value_with` to
+/// follow the naming config instead.
+pub fn to_value<T>(value: T) -> Result<Value, SurrealResponseError>
+where
+    T: Serialize + 'static,
+{
+    surrealdb::sql::to_value(value).map_err(|e| SurrealResponseError::SerdeError(e.to_string()))
+}
+
+/// Convert a value into any serde type, reading the keys named by its serde attributes.
+///
+/// Like `to_value`, this ignores `SurrealDeriveConfig::use_camel_case`, see `from_value_with`.
+pub fn from_value<T>(value: &Value) -> Result<T, SurrealResponseError>
+where
+    T: DeserializeOwned,
+{
+    surrealdb::sql::from_value(value.clone())
+        .map_err(|e| SurrealResponseError::SerdeError(e.to_string()).mismatch::<T>(value))
+}
+
+/// Same as `to_value`, then turns the snake_case keys into camelCase when the config asks for it.
+///
+/// Once serialized, a struct can not be told apart from a map, so with `use_camel_case` every
+/// object key is renamed, the keys of maps included.
+pub fn to_value_with<T>(
+    value: T,
+    config: &SurrealDeriveConfig,
+) -> Result<Value, SurrealResponseError>
+where
+    T: Serialize + 'static,
+{
+    let value = to_value(value)?;
+    Ok(if config.use_camel_case {
+        rename_keys(value, &snake_case_to_camel)
+    } else {
+        value
+    })
+}
+
+/// Same as `from_value`, reading the camelCase keys written by `to_value_with`.
+pub fn from_value_with<T>(
+    value: &Value,
+    config: &SurrealDeriveConfig,
+) -> Result<T, SurrealResponseError>
+where
+    T: DeserializeOwned,
+{
+    if config.use_camel_case {
+        from_value(&rename_keys(value.clone(), &camel_to_snake_case))
+    } else {
+        from_value(value)
+    }
+}
+
+fn rename_keys(value: Value, rename: &impl Fn(&str) -> String) -> Value {
+    match value {
+        Value::Object(object) => Value::Object(Object::from(
+            object
+                .0
+                .into_iter()
+                .map(|(key, value)| (rename(&key), rename_keys(value, rename)))
+                .collect::<BTreeMap<_, _>>(),
+        )),
+        Value::Array(array) => Value::Array(
+            array
+                .0
+                .into_iter()
+                .map(|value| rename_keys(value, rename))
+                .collect::<Vec<_>>()
+                .into(),
+        ),
+        value => value,
+    }
+}
+
+/// Use the serde implementation of `T` with `SurrealSerializer` and `SurrealDeserializer`, e.g.
+/// as a field of a derived record.
+///
+/// Keys are named as in `to_value`. Not every serde type can be represented as a value, e.g. a
+/// map with non-string keys: such a value serializes to `Value::None`, so use `try_serialize`
+/// when the type may hit that case. `serialize_ref` clones `T`, since surrealdb only serializes
+/// owned values.
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Default, Serialize, Deserialize)]
+#[serde(transparent)]
+pub struct Serde<T>(pub T);
+
+impl<T> Deref for Serde<T> {
+    type Target = T;
+
+    fn deref(&self) -> &Self::Target {
+        &self.0
+    }
+}
+
+impl<T> DerefMut for Serde<T> {
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        &mut self.0
+    }
+}
+
+impl<T> Serde<T>
+where
+    T: Serialize + 'static,
+{
+    pub fn try_serialize(self) -> Result<Value, SurrealResponseError> {
+        to_value(self.0)
+    }
+}
+
+impl<T> SurrealSerializer for Serde<T>
+where
+    T: Serialize + 'static,
+{
+    fn serialize(self) -> Value {
+        self.try_serialize().unwrap_or(Value::None)
+    }
+}
+
+impl<T> SurrealSerializerRef for Serde<T>
+where
+    T: Serialize + Clone + 'static,
+{
+    fn serialize_ref(&self) -> Value {
+        self.clone().serialize()
+    }
+}
+
+impl<T> SurrealDeserializer for Serde<T>
+where
+    T: DeserializeOwned,
+{
+    fn deserialize(value: &Value) -> Result<Self, SurrealResponseError> {
+        Ok(Serde(from_value(value)?))
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::HashMap;
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    #[serde(rename_all = "camelCase")]
+    struct Profile {
+        display_name: String,
+        login_count: u32,
+        settings: HashMap<String, String>,
+    }
+
+    #[test]
+    fn test_keys_follow_serde_attributes() {
+        let profile = Profile {
+            display_name: "tobie".to_owned(),
+            login_count: 3,
+            settings: HashMap::from([("user_id".to_owned(), "1".to_owned())]),
+        };
+
+        let value = to_value(profile.clone()).unwrap();
+        let Value::Object(object) = &value else {
+            panic!("Expected an object");
+        };
+        assert!(object.contains_key("displayName"));
+        assert!(object.contains_key("loginCount"));
+        let Some(Value::Object(settings)) = object.get("settings") else {
+            panic!("Expected an object");
+        };
+        assert!(settings.contains_key("user_id"));
+        assert_eq!(from_value::<Profile>(&value), Ok(profile));
+    }
+
+    #[test]
+    fn test_unrepresentable_types_are_an_error() {
+        let map = Serde(HashMap::from([((1, 2), "point".to_owned())]));
+        assert!(matches!(
+            map.clone().try_serialize(),
+            Err(SurrealResponseError::SerdeError(_))
+        ));
+        assert_eq!(map.serialize_ref(), Value::None);
+    }
+
+    #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+    struct Session {
+        user_name: String,
+        last_seen: Vec<Profile>,
+    }
+
+    #[test]
+    fn test_serde_field_round_trip() {
+        let session = Serde(Session {
+            user_name: "tobie".to_owned(),
+            last_seen: vec![],
+        });
+
+        let value = session.serialize_ref();
+        assert_eq!(value, SurrealSerializer::serialize(session.clone()));
+        assert_eq!(
+            <Serde<Session> as SurrealDeserializer>::deserialize(&value),
+            Ok(session)
+        );
+    }
+
+    #[test]
+    fn test_naming_config_is_explicit() {
+        let session = Session {
+            user_name: "tobie".to_owned(),
+            last_seen: vec![],
+        };
+        let camel_case = SurrealDeriveConfig {
+            use_camel_case: true,
+            ..Default::default()
+        };
+
+        let Value::Object(object) = to_value(session.clone()).unwrap() else {
+            panic!("Expected an object");
+        };
+        assert!(object.contains_key("user_name"));
+
+        let value = to_value_with(session.clone(), &camel_case).unwrap();
+        let Value::Object(object) = &value else {
+            panic!("Expected an object");
+        };
+        assert!(object.contains_key("userName"));
+        assert!(object.contains_key("lastSeen"));
+        assert_eq!(
+            from_value_with::<Session>(&value, &camel_case),
+            Ok(session.clone())
+        );
+        assert_eq!(
+            to_value_with(session.clone(), &SurrealDeriveConfig::default()),
+            to_value(session)
+        );
+    }
+}
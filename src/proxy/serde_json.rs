@@ -0,0 +1,203 @@
+//! Conversions between SurrealDB values and `serde_json::Value`.
+//!
+//! Values that JSON has no type for are mapped as follows:
+//!
+//! | SurrealDB        | JSON                                                |
+//! |------------------|-----------------------------------------------------|
+//! | `NONE`, `NULL`   | `null`                                              |
+//! | int, float       | number, non-finite floats are an error              |
+//! | decimal          | number if it is an `i64` or `u64`, otherwise string |
+//! | record id        | string, `table:id`                                  |
+//! | datetime         | RFC 3339 string                                     |
+//! | duration         | string, `1h30m`                                     |
+//! | uuid             | string                                              |
+//! | geometry         | GeoJSON object                                      |
+//! | bytes            | padded standard base64 string                       |
+//!
+//! Other values, such as params or subqueries, never come back from a query and are rejected.
+//!
+//! JSON strings always become SurrealDB strings. The proxy impls of `DateTime`, `Uuid` and
+//! `ByteBuf` accept those strings back, so typed fields still read the values written above.
+use std::collections::BTreeMap;
+
+use base64::Engine;
+use serde_json::{Map, Number as JsonNumber, Value as JsonValue};
+use surrealdb::sql::{Array, Number, Object, Value};
+
+use crate::proxy::default::{SurrealDeserializer, SurrealSerializer, SurrealSerializerRef, BASE64};
+use crate::surreal_qr::{SurrealQR, SurrealResponseError};
+
+fn json_from_value(value: &Value) -> Result<JsonValue, SurrealResponseError> {
+    match value {
+        Value::None | Value::Null => Ok(JsonValue::Null),
+        Value::Number(Number::Float(float)) => JsonNumber::from_f64(*float)
+            .map(JsonValue::Number)
+            .ok_or_else(|| SurrealResponseError::OutOfRange.mismatch::<JsonValue>(value)),
+        Value::Number(Number::Decimal(decimal)) if decimal.is_integer() => {
+            match (i64::try_from(*decimal), u64::try_from(*decimal)) {
+                (Ok(int), _) => Ok(JsonValue::from(int)),
+                (_, Ok(uint)) => Ok(JsonValue::from(uint)),
+                _ => Ok(JsonValue::String(decimal.to_string())),
+            }
+        }
+        Value::Number(Number::Decimal(decimal)) => Ok(JsonValue::String(decimal.to_string())),
+        Value::Bytes(bytes) => Ok(JsonValue::String(BASE64.encode(bytes.as_slice()))),
+        Value::Array(array) => array
+            .iter()
+            .enumerate()
+            .map(|(index, item)| json_from_value(item).map_err(|e| e.at_index(index)))
+            .collect(),
+        Value::Object(object) => object
+            .iter()
+            .map(|(key, item)| {
+                json_from_value(item)
+                    .map(|item| (key.clone(), item))
+                    .map_err(|e| e.at_field(key.as_str()))
+            })
+            .collect::<Result<Map<_, _>, _>>()
+            .map(JsonValue::Object),
+        Value::Bool(_)
+        | Value::Number(_)
+        | Value::Strand(_)
+        | Value::Duration(_)
+        | Value::Datetime(_)
+        | Value::Uuid(_)
+        | Value::Geometry(_)
+        | Value::Thing(_) => Ok(value.clone().into_json()),
+        _ => Err(SurrealResponseError::UnexpectedValueType.mismatch::<JsonValue>(value)),
+    }
+}
+
+fn value_from_json(json: JsonValue) -> Value {
+    match json {
+        JsonValue::Null => Value::Null,
+        JsonValue::Bool(boolean) => Value::Bool(boolean),
+        JsonValue::Number(number) => {
+            if let Some(int) = number.as_i64() {
+                Value::from(int)
+            } else if let Some(uint) = number.as_u64() {
+                uint.serialize()
+            } else {
+                Value::from(number.as_f64().unwrap_or(f64::NAN))
+            }
+        }
+        JsonValue::String(string) => Value::from(string),
+        JsonValue::Array(array) => Value::Array(Array::from(
+            array.into_iter().map(value_from_json).collect::<Vec<_>>(),
+        )),
+        JsonValue::Object(object) => Value::Object(Object::from(
+            object
+                .into_iter()
+                .map(|(key, item)| (key, value_from_json(item)))
+                .collect::<BTreeMap<_, _>>(),
+        )),
+    }
+}
+
+impl From<JsonValue> for SurrealQR {
+    fn from(value: JsonValue) -> Self {
+        SurrealQR(value_from_json(value))
+    }
+}
+
+impl TryFrom<SurrealQR> for JsonValue {
+    type Error = SurrealResponseError;
+
+    fn try_from(value: SurrealQR) -> Result<Self, Self::Error> {
+        json_from_value(&value.0)
+    }
+}
+
+impl TryFrom<&SurrealQR> for JsonValue {
+    type Error = SurrealResponseError;
+
+    fn try_from(value: &SurrealQR) -> Result<Self, Self::Error> {
+        json_from_value(&value.0)
+    }
+}
+
+impl SurrealSerializer for JsonValue {
+    fn serialize(self) -> Value {
+        value_from_json(self)
+    }
+}
+
+impl SurrealSerializerRef for JsonValue {
+    fn serialize_ref(&self) -> Value {
+        value_from_json(self.clone())
+    }
+}
+
+impl SurrealDeserializer for JsonValue {
+    fn deserialize(value: &Value) -> Result<JsonValue, SurrealResponseError> {
+        json_from_value(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use serde_json::json;
+    use surrealdb::sql::{Bytes, Thing};
+
+    #[test]
+    fn test_json_round_trip() {
+        let json = json!({
+            "name": "surreal",
+            "tags": ["a", "b"],
+            "count": 3,
+            "big": u64::MAX,
+            "ratio": 0.5,
+            "nested": { "ok": true, "missing": null },
+        });
+
+        let qr = SurrealQR::from(json.clone());
+        assert_eq!(JsonValue::try_from(&qr), Ok(json.clone()));
+        assert_eq!(
+            JsonValue::deserialize(&json.clone().serialize()),
+            Ok(json.clone())
+        );
+        assert_eq!(json.serialize_ref(), qr.0);
+    }
+
+    #[test]
+    fn test_null_fields_are_missing() {
+        let qr = SurrealQR::from(json!({ "name": null, "tags": null }));
+        assert_eq!(qr.get_opt::<String, _>("name"), Ok(None));
+        assert_eq!(qr.get_or_default::<Vec<String>, _>("tags"), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_surreal_only_values() {
+        let value = Value::from(vec![
+            Value::from(Thing::from(("user", "john"))),
+            Value::Bytes(Bytes::from(vec![0, 1, 254, 255])),
+        ]);
+        assert_eq!(
+            JsonValue::try_from(SurrealQR(value)),
+            Ok(json!(["user:john", "AAH+/w=="]))
+        );
+
+        let error = JsonValue::try_from(SurrealQR(Value::from(vec![
+            Value::from(1),
+            Value::from(f64::INFINITY),
+        ])))
+        .unwrap_err();
+        assert_eq!(error.kind(), &SurrealResponseError::OutOfRange);
+        assert_eq!(error.path().unwrap().to_string(), "[1]");
+    }
+
+    #[test]
+    fn test_decimals() {
+        let decimal = Value::Number(Number::Decimal("1.50".parse().unwrap()));
+        assert_eq!(JsonValue::deserialize(&decimal), Ok(json!("1.50")));
+        for int in [-5, 5] {
+            let decimal = Value::Number(Number::Decimal(int.into()));
+            assert_eq!(JsonValue::deserialize(&decimal), Ok(json!(int)));
+        }
+        assert_eq!(
+            JsonValue::deserialize(&u64::MAX.serialize()),
+            Ok(json!(u64::MAX))
+        );
+    }
+}
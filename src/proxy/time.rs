@@ -0,0 +1,222 @@
+//! The `time` types go through their chrono counterparts, so they share the same mapping:
+//! datetimes are stored as datetimes in UTC with nanosecond precision, dates at midnight UTC,
+//! times as ISO 8601 strings and durations as durations, except negative ones.
+//!
+//! With the `large-dates` feature of `time`, a year can go beyond the range of chrono. Such a
+//! value is stored as its `Display` string instead, which is an error to read back.
+use std::str::FromStr;
+
+use chrono::{DateTime, Datelike, FixedOffset, NaiveDate, NaiveDateTime, NaiveTime, Timelike, Utc};
+use surrealdb::sql::Value;
+use time::{Date, Duration, Month, OffsetDateTime, PrimitiveDateTime, Time, UtcOffset};
+
+use crate::proxy::default::{
+    copy_serializer_ref_impls, SurrealDeserializer, SurrealSerializer, SurrealSerializerRef,
+};
+use crate::surreal_qr::SurrealResponseError;
+
+fn date_to_chrono(date: Date) -> Option<NaiveDate> {
+    NaiveDate::from_ymd_opt(date.year(), date.month() as u32, date.day() as u32)
+}
+
+fn date_from_chrono<T>(date: NaiveDate, value: &Value) -> Result<Date, SurrealResponseError> {
+    Month::try_from(date.month() as u8)
+        .and_then(|month| Date::from_calendar_date(date.year(), month, date.day() as u8))
+        .map_err(|_| SurrealResponseError::OutOfRange.mismatch::<T>(value))
+}
+
+fn time_to_chrono(time: Time) -> Option<NaiveTime> {
+    NaiveTime::from_hms_nano_opt(
+        time.hour() as u32,
+        time.minute() as u32,
+        time.second() as u32,
+        time.nanosecond(),
+    )
+}
+
+fn time_from_chrono<T>(time: NaiveTime, value: &Value) -> Result<Time, SurrealResponseError> {
+    Time::from_hms_nano(
+        time.hour() as u8,
+        time.minute() as u8,
+        time.second() as u8,
+        time.nanosecond(),
+    )
+    .map_err(|_| SurrealResponseError::OutOfRange.mismatch::<T>(value))
+}
+
+impl SurrealSerializer for OffsetDateTime {
+    fn serialize(self) -> Value {
+        match DateTime::<Utc>::from_timestamp(self.unix_timestamp(), self.nanosecond()) {
+            Some(datetime) => datetime.serialize(),
+            None => Value::from(self.to_string()),
+        }
+    }
+}
+
+/// Keeps the offset of RFC 3339 strings, native datetimes are read in UTC.
+impl SurrealDeserializer for OffsetDateTime {
+    fn deserialize(value: &Value) -> Result<OffsetDateTime, SurrealResponseError> {
+        let datetime = DateTime::<FixedOffset>::deserialize(value)?;
+        let out_of_range = |_| SurrealResponseError::OutOfRange.mismatch::<Self>(value);
+        let offset = UtcOffset::from_whole_seconds(datetime.offset().local_minus_utc())
+            .map_err(out_of_range)?;
+
+        Ok(OffsetDateTime::from_unix_timestamp(datetime.timestamp())
+            .map_err(out_of_range)?
+            .replace_nanosecond(datetime.timestamp_subsec_nanos())
+            .map_err(out_of_range)?
+            .to_offset(offset))
+    }
+}
+
+impl SurrealSerializer for PrimitiveDateTime {
+    fn serialize(self) -> Value {
+        match date_to_chrono(self.date()).zip(time_to_chrono(self.time())) {
+            Some((date, time)) => NaiveDateTime::new(date, time).serialize(),
+            None => Value::from(self.to_string()),
+        }
+    }
+}
+
+impl SurrealDeserializer for PrimitiveDateTime {
+    fn deserialize(value: &Value) -> Result<PrimitiveDateTime, SurrealResponseError> {
+        let datetime = NaiveDateTime::deserialize(value)?;
+        Ok(PrimitiveDateTime::new(
+            date_from_chrono::<Self>(datetime.date(), value)?,
+            time_from_chrono::<Self>(datetime.time(), value)?,
+        ))
+    }
+}
+
+impl SurrealSerializer for Date {
+    fn serialize(self) -> Value {
+        match date_to_chrono(self) {
+            Some(date) => date.serialize(),
+            None => Value::from(self.to_string()),
+        }
+    }
+}
+
+impl SurrealDeserializer for Date {
+    fn deserialize(value: &Value) -> Result<Date, SurrealResponseError> {
+        date_from_chrono::<Self>(NaiveDate::deserialize(value)?, value)
+    }
+}
+
+impl SurrealSerializer for Time {
+    fn serialize(self) -> Value {
+        match time_to_chrono(self) {
+            Some(time) => time.serialize(),
+            None => Value::from(self.to_string()),
+        }
+    }
+}
+
+impl SurrealDeserializer for Time {
+    fn deserialize(value: &Value) -> Result<Time, SurrealResponseError> {
+        time_from_chrono::<Self>(NaiveTime::deserialize(value)?, value)
+    }
+}
+
+/// SurrealDB durations are unsigned, so a negative duration is stored as a string such as
+/// `-1h30m`.
+impl SurrealSerializer for Duration {
+    fn serialize(self) -> Value {
+        if self.is_negative() {
+            Value::from(format!(
+                "-{}",
+                surrealdb::sql::Duration::from(self.unsigned_abs())
+            ))
+        } else {
+            self.unsigned_abs().serialize()
+        }
+    }
+}
+
+impl SurrealDeserializer for Duration {
+    fn deserialize(value: &Value) -> Result<Duration, SurrealResponseError> {
+        match value {
+            Value::Strand(strand) if strand.starts_with('-') => negative_duration(&strand[1..])
+                .ok_or_else(|| SurrealResponseError::ExpectedADuration.mismatch::<Self>(value)),
+            _ => Duration::try_from(std::time::Duration::deserialize(value)?)
+                .map_err(|_| SurrealResponseError::OutOfRange.mismatch::<Self>(value)),
+        }
+    }
+}
+
+fn negative_duration(magnitude: &str) -> Option<Duration> {
+    let magnitude = surrealdb::sql::Duration::from_str(magnitude).ok()?;
+    let seconds = i64::try_from(-i128::from(magnitude.as_secs())).ok()?;
+    Some(Duration::new(seconds, -(magnitude.subsec_nanos() as i32)))
+}
+
+copy_serializer_ref_impls!(OffsetDateTime, PrimitiveDateTime, Date, Time, Duration);
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use time::macros::datetime;
+
+    #[test]
+    fn test_round_trip() {
+        let datetime = datetime!(2024-01-31 10:30:00.123456789 +07:00);
+        assert_eq!(
+            OffsetDateTime::deserialize(&datetime.serialize()),
+            Ok(datetime)
+        );
+        assert_eq!(
+            PrimitiveDateTime::deserialize(
+                &PrimitiveDateTime::new(datetime.date(), datetime.time()).serialize()
+            ),
+            Ok(PrimitiveDateTime::new(datetime.date(), datetime.time()))
+        );
+        assert_eq!(
+            Date::deserialize(&datetime.date().serialize()),
+            Ok(datetime.date())
+        );
+        assert_eq!(
+            Time::deserialize(&datetime.time().serialize()),
+            Ok(datetime.time())
+        );
+
+        let duration = Duration::nanoseconds(1_500_000_001);
+        assert_eq!(Duration::deserialize(&duration.serialize()), Ok(duration));
+    }
+
+    #[test]
+    fn test_negative_durations() {
+        let duration = Duration::minutes(-90);
+        assert_eq!(duration.serialize(), Value::from("-1h30m"));
+        for duration in [duration, Duration::nanoseconds(-1), Duration::MIN] {
+            assert_eq!(Duration::deserialize(&duration.serialize()), Ok(duration));
+        }
+        assert_eq!(
+            Duration::deserialize(&Value::from("-x"))
+                .unwrap_err()
+                .kind(),
+            &SurrealResponseError::ExpectedADuration
+        );
+    }
+
+    #[test]
+    fn test_dates_beyond_chrono_are_strings() {
+        let date = Date::from_calendar_date(300_000, Month::January, 1).unwrap();
+        let datetime = date.with_hms(10, 30, 0).unwrap();
+
+        assert_eq!(date.serialize(), Value::from(date.to_string()));
+        assert!(Date::deserialize(&date.serialize()).is_err());
+        assert_eq!(datetime.serialize(), Value::from(datetime.to_string()));
+        assert!(PrimitiveDateTime::deserialize(&datetime.serialize()).is_err());
+        let datetime = datetime.assume_utc();
+        assert_eq!(datetime.serialize(), Value::from(datetime.to_string()));
+        assert!(OffsetDateTime::deserialize(&datetime.serialize()).is_err());
+    }
+
+    #[test]
+    fn test_reads_rfc3339_strings() {
+        assert_eq!(
+            OffsetDateTime::deserialize(&Value::from("2024-01-31T10:30:00+07:00")),
+            Ok(datetime!(2024-01-31 10:30:00 +07:00))
+        );
+    }
+}
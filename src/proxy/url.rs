@@ -0,0 +1,46 @@
+use surrealdb::sql::Value;
+use url::Url;
+
+use crate::proxy::default::{
+    parse_strand, SurrealDeserializer, SurrealSerializer, SurrealSerializerRef,
+};
+use crate::surreal_qr::SurrealResponseError;
+
+impl SurrealSerializer for Url {
+    fn serialize(self) -> Value {
+        Value::from(String::from(self))
+    }
+}
+
+impl SurrealSerializerRef for Url {
+    fn serialize_ref(&self) -> Value {
+        Value::from(self.as_str())
+    }
+}
+
+impl SurrealDeserializer for Url {
+    fn deserialize(value: &Value) -> Result<Url, SurrealResponseError> {
+        parse_strand(value)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_round_trip() {
+        let url = Url::parse("https://example.com/callback?id=1").unwrap();
+        assert_eq!(
+            url.serialize_ref(),
+            Value::from("https://example.com/callback?id=1")
+        );
+        assert_eq!(Url::deserialize(&url.clone().serialize()), Ok(url));
+        assert!(matches!(
+            Url::deserialize(&Value::from("not a url"))
+                .unwrap_err()
+                .kind(),
+            SurrealResponseError::ParseError(_)
+        ));
+    }
+}
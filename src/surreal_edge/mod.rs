@@ -1,4 +1,4 @@
-use crate::proxy::default::{SurrealDeserializer, SurrealSerializer};
+use crate::proxy::default::{SurrealDeserializer, SurrealSerializer, SurrealSerializerRef};
 use crate::surreal_id::{Link, SurrealId};
 use crate::surreal_qr::SurrealResponseError;
 use serde::{Deserialize, Serialize};
@@ -66,25 +66,25 @@ where
     }
 }
 
-impl<I, R, O> Into<Thing> for Edge<I, R, O>
+impl<I, R, O> From<Edge<I, R, O>> for Thing
 where
     R: SurrealSerializer + SurrealId,
     I: SurrealId,
     O: SurrealId,
 {
-    fn into(self) -> Thing {
-        self.data.id()
+    fn from(value: Edge<I, R, O>) -> Self {
+        value.data.id()
     }
 }
 
-impl<I, R, O> Into<Value> for Edge<I, R, O>
+impl<I, R, O> From<Edge<I, R, O>> for Value
 where
     R: SurrealSerializer + SurrealId,
     I: SurrealId,
     O: SurrealId,
 {
-    fn into(self) -> Value {
-        Value::from(self.id())
+    fn from(value: Edge<I, R, O>) -> Self {
+        Value::from(value.id())
     }
 }
 
@@ -99,14 +99,14 @@ where
     }
 }
 
-impl<I, R, O> Into<Thing> for &Edge<I, R, O>
+impl<I, R, O> From<&Edge<I, R, O>> for Thing
 where
     R: SurrealSerializer + SurrealId,
     I: SurrealId,
     O: SurrealId,
 {
-    fn into(self) -> Thing {
-        (&self.data).id()
+    fn from(value: &Edge<I, R, O>) -> Self {
+        value.data.id()
     }
 }
 
@@ -121,6 +121,17 @@ where
     }
 }
 
+impl<I, R, O> SurrealSerializerRef for Edge<I, R, O>
+where
+    R: SurrealSerializer + SurrealId,
+    I: SurrealId,
+    O: SurrealId,
+{
+    fn serialize_ref(&self) -> Value {
+        Value::from(self.data.id())
+    }
+}
+
 impl<I, R, O> SurrealDeserializer for Edge<I, R, O>
 where
     R: SurrealSerializer + SurrealId + SurrealDeserializer,
@@ -133,15 +144,16 @@ where
             Value::Array(arr) => {
                 if arr.len() != 1 {
                     return Err(
-                        SurrealResponseError::ExpectedAnArrayWith1ItemToDeserializeToObject,
+                        SurrealResponseError::ExpectedAnArrayWith1ItemToDeserializeToObject
+                            .mismatch::<Self>(value),
                     );
                 } else if let Some(Value::Object(obj)) = arr.0.first() {
                     obj
                 } else {
-                    return Err(SurrealResponseError::ExpectedAnObject);
+                    return Err(SurrealResponseError::ExpectedAnObject.mismatch::<Self>(value));
                 }
             }
-            _ => return Err(SurrealResponseError::ExpectedAnObject),
+            _ => return Err(SurrealResponseError::ExpectedAnObject.mismatch::<Self>(value)),
         };
 
         let in_value = object.get("in");
@@ -149,14 +161,104 @@ where
 
         Ok(Self {
             r#in: match in_value {
-                Some(value) => Some(SurrealDeserializer::deserialize(value)?),
+                Some(value) => {
+                    Some(SurrealDeserializer::deserialize(value).map_err(|e| e.at_field("in"))?)
+                }
                 None => None,
             },
             r#out: match out_value {
-                Some(value) => Some(SurrealDeserializer::deserialize(value)?),
+                Some(value) => {
+                    Some(SurrealDeserializer::deserialize(value).map_err(|e| e.at_field("out"))?)
+                }
                 None => None,
             },
             data: SurrealDeserializer::deserialize(&Value::Object(object.clone()))?,
         })
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::BTreeMap;
+    use surrealdb::sql::Object;
+
+    #[derive(Debug, Clone, PartialEq)]
+    struct User {
+        id: Thing,
+        name: String,
+    }
+
+    impl SurrealId for User {
+        fn id(&self) -> Thing {
+            self.id.clone()
+        }
+    }
+
+    impl SurrealSerializer for User {
+        fn serialize(self) -> Value {
+            Value::Object(Object::from(BTreeMap::from([
+                ("id".to_owned(), Value::from(self.id)),
+                ("name".to_owned(), Value::from(self.name)),
+            ])))
+        }
+    }
+
+    impl SurrealDeserializer for User {
+        fn deserialize(value: &Value) -> Result<Self, SurrealResponseError> {
+            let Value::Object(object) = value else {
+                return Err(SurrealResponseError::ExpectedAnObject.mismatch::<Self>(value));
+            };
+
+            Ok(User {
+                id: Thing::from_field(object, "id")?,
+                name: String::from_field(object, "name")?,
+            })
+        }
+    }
+
+    fn object<const N: usize>(entries: [(&str, Value); N]) -> Value {
+        Value::Object(Object::from(
+            entries
+                .into_iter()
+                .map(|(key, value)| (key.to_owned(), value))
+                .collect::<BTreeMap<_, _>>(),
+        ))
+    }
+
+    #[test]
+    fn test_in_and_out_errors_are_prefixed() {
+        let user = |name: Value| {
+            object([
+                ("id", Value::from(Thing::from(("user", "john")))),
+                ("name", name),
+            ])
+        };
+        let edge = |r#in: Value, out: Value| {
+            object([
+                ("id", Value::from(Thing::from(("follows", "1")))),
+                ("name", Value::from("follows")),
+                ("in", r#in),
+                ("out", out),
+            ])
+        };
+
+        let valid = edge(user(Value::from("john")), user(Value::from("jane")));
+        let read = Edge::<User, User, User>::deserialize(&valid).unwrap();
+        assert_eq!(read.r#in.unwrap().record().name, "john");
+
+        let error = Edge::<User, User, User>::deserialize(&edge(
+            user(Value::from(1)),
+            user(Value::from("jane")),
+        ))
+        .unwrap_err();
+        assert_eq!(error.path().unwrap().to_string(), "in.name");
+        assert_eq!(error.kind(), &SurrealResponseError::ExpectedAStrand);
+
+        let error =
+            Edge::<User, User, User>::deserialize(&edge(user(Value::from("john")), Value::from(1)))
+                .unwrap_err();
+        assert_eq!(error.path().unwrap().to_string(), "out");
+        assert_eq!(error.kind(), &SurrealResponseError::ExpectedAnObject);
+    }
+}
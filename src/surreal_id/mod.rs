@@ -3,7 +3,7 @@ use std::ops::Deref;
 use surrealdb::sql::{Thing, Value};
 
 use crate::{
-    proxy::default::{SurrealDeserializer, SurrealSerializer},
+    proxy::default::{SurrealDeserializer, SurrealSerializer, SurrealSerializerRef},
     surreal_qr::SurrealResponseError,
 };
 
@@ -51,21 +51,21 @@ where
     }
 }
 
-impl<T> Into<Thing> for Link<T>
+impl<T> From<Link<T>> for Thing
 where
     T: SurrealId,
 {
-    fn into(self) -> Thing {
-        self.id()
+    fn from(value: Link<T>) -> Self {
+        value.id()
     }
 }
 
-impl<T> Into<Thing> for &Link<T>
+impl<T> From<&Link<T>> for Thing
 where
     T: SurrealId,
 {
-    fn into(self) -> Thing {
-        self.id().clone()
+    fn from(value: &Link<T>) -> Self {
+        value.id()
     }
 }
 
@@ -80,7 +80,7 @@ where
             Link::Id(_) => {
                 panic!("The link can not be deref, it must be Link::Record(T) to be deref")
             }
-            Link::Record(r) => &r,
+            Link::Record(r) => r,
         }
     }
 }
@@ -94,6 +94,15 @@ where
     }
 }
 
+impl<T> SurrealSerializerRef for Link<T>
+where
+    T: SurrealId,
+{
+    fn serialize_ref(&self) -> Value {
+        Value::from(self.id())
+    }
+}
+
 impl<T> SurrealDeserializer for Link<T>
 where
     T: SurrealId + SurrealDeserializer,
@@ -107,15 +116,18 @@ where
                 Value::Array(arr) => {
                     if arr.len() != 1 {
                         return Err(
-                            SurrealResponseError::ExpectedAnArrayWith1ItemToDeserializeToObject,
+                            SurrealResponseError::ExpectedAnArrayWith1ItemToDeserializeToObject
+                                .mismatch::<Link<T>>(value),
                         );
                     } else if let Some(Value::Object(obj)) = arr.0.first() {
                         obj.clone()
                     } else {
-                        return Err(SurrealResponseError::ExpectedAnObject);
+                        return Err(
+                            SurrealResponseError::ExpectedAnObject.mismatch::<Link<T>>(value)
+                        );
                     }
                 }
-                _ => return Err(SurrealResponseError::ExpectedAnObject),
+                _ => return Err(SurrealResponseError::ExpectedAnObject.mismatch::<Link<T>>(value)),
             };
 
             Ok(Link::Record(T::deserialize(&Value::Object(object))?))
@@ -128,3 +140,53 @@ impl SurrealId for Thing {
         self.clone()
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::BTreeMap;
+    use surrealdb::sql::Object;
+
+    #[derive(Debug)]
+    struct Tag(Thing);
+
+    impl SurrealId for Tag {
+        fn id(&self) -> Thing {
+            self.0.clone()
+        }
+    }
+
+    impl SurrealDeserializer for Tag {
+        fn deserialize(value: &Value) -> Result<Self, SurrealResponseError> {
+            let Value::Object(object) = value else {
+                return Err(SurrealResponseError::ExpectedAnObject.mismatch::<Self>(value));
+            };
+
+            Ok(Tag(Thing::from_field(object, "id")?))
+        }
+    }
+
+    #[test]
+    fn test_link_errors() {
+        let id = Thing::from(("tag", "rust"));
+        assert_eq!(
+            Link::<Tag>::deserialize(&Value::from(id.clone())).map(|link| link.id()),
+            Ok(id)
+        );
+
+        let record = Value::Object(Object::from(BTreeMap::from([(
+            "id".to_owned(),
+            Value::from("rust"),
+        )])));
+        let error = Link::<Tag>::deserialize(&record).unwrap_err();
+        assert_eq!(error.path().unwrap().to_string(), "id");
+        assert_eq!(error.kind(), &SurrealResponseError::ExpectedAThing);
+
+        let error = Link::<Tag>::deserialize(&Value::from(1)).unwrap_err();
+        assert_eq!(error.kind(), &SurrealResponseError::ExpectedAnObject);
+        assert_eq!(
+            error.path().map(|path| path.to_string()),
+            Some(String::new())
+        );
+    }
+}
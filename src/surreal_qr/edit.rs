@@ -0,0 +1,409 @@
+//! Path based editing of a `SurrealQR`, e.g. to patch a fetched document before saving it.
+//!
+//! Edits follow the same rules as `SurrealQR::get`, an array holding a single object being
+//! edited as that object. Paths that select several values are rejected. The path is checked
+//! before anything is created, so an edit that fails leaves the value unchanged.
+use std::borrow::Cow;
+use std::mem;
+
+use surrealdb::sql::{Array, Object, Value};
+
+use super::path::{self, Segment};
+use super::{array_of, object_of, position, signed_index, RPath, SurrealQR, SurrealResponseError};
+use crate::proxy::default::SurrealSerializer;
+
+impl SurrealQR {
+    /// Borrow the value at `path` mutably, `None` when it is missing.
+    pub fn get_mut<'a, T>(&mut self, path: T) -> Result<Option<&mut Value>, SurrealResponseError>
+    where
+        T: Into<RPath<'a>>,
+    {
+        let path = path.into();
+        let mut current = &mut self.0;
+        for segment in segments(&path)? {
+            match step_mut(current, &segment)? {
+                Some(value) => current = value,
+                None => return Ok(None),
+            }
+        }
+
+        Ok(Some(current))
+    }
+
+    /// Replace the value at `path`, creating the missing objects on the way.
+    ///
+    /// Arrays are never created or extended, an index must point to an existing item.
+    pub fn set<'a, T, V>(&mut self, path: T, value: V) -> Result<(), SurrealResponseError>
+    where
+        T: Into<RPath<'a>>,
+        V: SurrealSerializer,
+    {
+        let path = path.into();
+        let segments = segments(&path)?;
+        target(&self.0, &segments)?;
+        *self.entry_at(&segments)? = value.serialize();
+        Ok(())
+    }
+
+    /// Remove the value at `path` from its object or array, returning it.
+    pub fn remove<'a, T>(&mut self, path: T) -> Result<Option<Value>, SurrealResponseError>
+    where
+        T: Into<RPath<'a>>,
+    {
+        let path = path.into();
+        let mut segments = segments(&path)?;
+        let Some(last) = segments.pop() else {
+            return Ok(Some(mem::take(&mut self.0)).filter(|value| !value.is_none()));
+        };
+
+        let mut current = &mut self.0;
+        for segment in segments {
+            match step_mut(current, &segment)? {
+                Some(value) => current = value,
+                None => return Ok(None),
+            }
+        }
+
+        match last {
+            Segment::Field(field) => Ok(object_of_mut(current)?.and_then(|o| o.remove(&*field))),
+            Segment::Index(index) => match array_of_mut(current)? {
+                Some(array) => {
+                    let position = position(index, array.len())?;
+                    Ok(Some(array.remove(position)))
+                }
+                None => Ok(None),
+            },
+            _ => Err(SurrealResponseError::AmbiguousPath),
+        }
+    }
+
+    /// Insert `value` in the array at `path` before the index of its last segment, shifting the
+    /// following items. A negative index counts from the end, `-1` appending the value.
+    ///
+    /// When the last segment is a field, this is the same as `set`.
+    pub fn insert<'a, T, V>(&mut self, path: T, value: V) -> Result<(), SurrealResponseError>
+    where
+        T: Into<RPath<'a>>,
+        V: SurrealSerializer,
+    {
+        let path = path.into();
+        let segments = segments(&path)?;
+        let (index, parents) = match segments.split_last() {
+            Some((Segment::Index(index), parents)) => (*index, parents),
+            _ => {
+                target(&self.0, &segments)?;
+                *self.entry_at(&segments)? = value.serialize();
+                return Ok(());
+            }
+        };
+
+        let len = match target(&self.0, parents)? {
+            Some(Value::Array(array)) => array.len(),
+            Some(_) => return Err(SurrealResponseError::ExpectedAnArray),
+            None => 0,
+        };
+        let at = if index < 0 {
+            usize::try_from(index.unsigned_abs())
+                .ok()
+                .and_then(|n| (len + 1).checked_sub(n))
+        } else {
+            usize::try_from(index).ok().filter(|i| *i <= len)
+        };
+
+        let at = at.ok_or(SurrealResponseError::IndexOutOfRange { index, len })?;
+        as_array(self.entry_at(parents)?)?.insert(at, value.serialize());
+        Ok(())
+    }
+
+    /// Append `value` to the array at `path`, creating the array and the missing objects on the
+    /// way.
+    pub fn push<'a, T, V>(&mut self, path: T, value: V) -> Result<(), SurrealResponseError>
+    where
+        T: Into<RPath<'a>>,
+        V: SurrealSerializer,
+    {
+        let path = path.into();
+        let segments = segments(&path)?;
+        if let Some(target) = target(&self.0, &segments)? {
+            array_of(target)?;
+        }
+
+        as_array(self.entry_at(&segments)?)?.push(value.serialize());
+        Ok(())
+    }
+
+    fn entry_at(&mut self, segments: &[Segment]) -> Result<&mut Value, SurrealResponseError> {
+        let mut current = &mut self.0;
+        for segment in segments {
+            current = entry_mut(current, segment)?;
+        }
+
+        Ok(current)
+    }
+}
+
+/// Flatten `path` into the fields and indices it walks through.
+fn segments<'p>(path: &'p RPath) -> Result<Vec<Segment<'p>>, SurrealResponseError> {
+    let mut segments = Vec::new();
+    push_segments(path, &mut segments)?;
+    Ok(segments)
+}
+
+fn push_segments<'p>(
+    path: &'p RPath,
+    segments: &mut Vec<Segment<'p>>,
+) -> Result<(), SurrealResponseError> {
+    match path {
+        RPath::Index(_) | RPath::Last | RPath::FromEnd(_) => {
+            segments.push(Segment::Index(signed_index(path)?))
+        }
+        RPath::Field(field) => segments.push(Segment::Field(Cow::Borrowed(field))),
+        RPath::Chain(chain) => {
+            for path in chain {
+                push_segments(path, segments)?;
+            }
+        }
+        RPath::Path(path) => {
+            for segment in path::parse(path)? {
+                match segment {
+                    Segment::Field(_) | Segment::Index(_) => segments.push(segment),
+                    _ => return Err(SurrealResponseError::AmbiguousPath),
+                }
+            }
+        }
+        RPath::All | RPath::Slice { .. } | RPath::Filter { .. } => {
+            return Err(SurrealResponseError::AmbiguousPath)
+        }
+    }
+
+    Ok(())
+}
+
+fn object_of_mut(value: &mut Value) -> Result<Option<&mut Object>, SurrealResponseError> {
+    match value {
+        Value::None => Ok(None),
+        Value::Object(object) => Ok(Some(object)),
+        Value::Array(array) => match array.0.as_mut_slice() {
+            [] => Ok(None),
+            [Value::Object(object)] => Ok(Some(object)),
+            [_] => Err(SurrealResponseError::ExpectedAnObject),
+            _ => Err(SurrealResponseError::ExpectedAnArrayWith1ItemToDeserializeToObject),
+        },
+        _ => Err(SurrealResponseError::ExpectedAnObject),
+    }
+}
+
+fn array_of_mut(value: &mut Value) -> Result<Option<&mut Array>, SurrealResponseError> {
+    match value {
+        Value::None => Ok(None),
+        Value::Array(array) => Ok(Some(array)),
+        _ => Err(SurrealResponseError::ExpectedAnArray),
+    }
+}
+
+/// Read `value` as an array, a none or null value becoming an empty array.
+fn as_array(value: &mut Value) -> Result<&mut Array, SurrealResponseError> {
+    if value.is_none_or_null() {
+        *value = Value::Array(Array::new());
+    }
+
+    match value {
+        Value::Array(array) => Ok(array),
+        _ => Err(SurrealResponseError::ExpectedAnArray),
+    }
+}
+
+/// Walk one segment, `None` when the value is missing.
+fn step_mut<'v>(
+    value: &'v mut Value,
+    segment: &Segment,
+) -> Result<Option<&'v mut Value>, SurrealResponseError> {
+    if value.is_none_or_null() {
+        return Ok(None);
+    }
+
+    match segment {
+        Segment::Field(field) => Ok(object_of_mut(value)?.and_then(|o| o.get_mut(&**field))),
+        Segment::Index(index) => match array_of_mut(value)? {
+            Some(array) => {
+                let position = position(*index, array.len())?;
+                Ok(Some(&mut array[position]))
+            }
+            None => Ok(None),
+        },
+        _ => Err(SurrealResponseError::AmbiguousPath),
+    }
+}
+
+/// Check that `segments` can be walked by `entry_mut` without failing, so an edit that would fail
+/// leaves the value unchanged. Returns the value at the end, `None` when it would be created.
+fn target<'v>(
+    value: &'v Value,
+    segments: &[Segment],
+) -> Result<Option<&'v Value>, SurrealResponseError> {
+    let mut current = Some(value).filter(|value| !value.is_none_or_null());
+    for segment in segments {
+        current = match (current, segment) {
+            (Some(value), Segment::Field(field)) => object_of(value)?
+                .ok_or(SurrealResponseError::ExpectedAnObject)?
+                .get(&**field),
+            (Some(value), Segment::Index(index)) => match array_of(value)? {
+                Some(array) => Some(&array[position(*index, array.len())?]),
+                None => None,
+            },
+            (None, Segment::Field(_)) => None,
+            (None, Segment::Index(index)) => {
+                return Err(SurrealResponseError::IndexOutOfRange {
+                    index: *index,
+                    len: 0,
+                })
+            }
+            _ => return Err(SurrealResponseError::AmbiguousPath),
+        }
+        .filter(|value| !value.is_none_or_null());
+    }
+
+    Ok(current)
+}
+
+/// Walk one segment, creating the object or the field when it is missing.
+fn entry_mut<'v>(
+    value: &'v mut Value,
+    segment: &Segment,
+) -> Result<&'v mut Value, SurrealResponseError> {
+    match segment {
+        Segment::Field(field) => {
+            if value.is_none_or_null() {
+                *value = Value::Object(Object::default());
+            }
+
+            let object = object_of_mut(value)?.ok_or(SurrealResponseError::ExpectedAnObject)?;
+            Ok(object.entry(field.to_string()).or_insert(Value::None))
+        }
+        Segment::Index(index) => match step_mut(value, segment)? {
+            Some(item) => Ok(item),
+            None => Err(SurrealResponseError::IndexOutOfRange {
+                index: *index,
+                len: 0,
+            }),
+        },
+        _ => Err(SurrealResponseError::AmbiguousPath),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    fn document() -> SurrealQR {
+        SurrealQR(Value::Object(Object::from(BTreeMap::from([
+            ("name".to_owned(), Value::from("john")),
+            (
+                "tags".to_owned(),
+                Value::from(vec![Value::from("a"), Value::from("b")]),
+            ),
+        ]))))
+    }
+
+    #[test]
+    fn test_get_mut_and_set() {
+        let mut qr = document();
+        *qr.get_mut("tags[-1]").unwrap().unwrap() = Value::from("c");
+        assert_eq!(qr.get("tags[1]").unwrap().0, Value::from("c"));
+        assert!(qr.get_mut("missing.field").unwrap().is_none());
+
+        qr.set("name", "jane".to_owned()).unwrap();
+        qr.set("address.city", "paris".to_owned()).unwrap();
+        assert_eq!(qr.get("name").unwrap().0, Value::from("jane"));
+        assert_eq!(qr.get("address.city").unwrap().0, Value::from("paris"));
+
+        assert_eq!(
+            qr.set("tags[5]", 1).unwrap_err(),
+            SurrealResponseError::IndexOutOfRange { index: 5, len: 2 }
+        );
+        assert_eq!(
+            qr.set("tags[*]", 1).unwrap_err(),
+            SurrealResponseError::AmbiguousPath
+        );
+        assert_eq!(
+            qr.set("name.first", 1).unwrap_err(),
+            SurrealResponseError::ExpectedAnObject
+        );
+    }
+
+    #[test]
+    fn test_failed_edits_leave_the_document_unchanged() {
+        let mut qr = SurrealQR(Value::Object(Object::default()));
+        assert_eq!(
+            qr.set("x.y[0]", 1).unwrap_err(),
+            SurrealResponseError::IndexOutOfRange { index: 0, len: 0 }
+        );
+        assert_eq!(
+            qr.insert("x.y[1]", 1).unwrap_err(),
+            SurrealResponseError::IndexOutOfRange { index: 1, len: 0 }
+        );
+        assert_eq!(qr.0, Value::Object(Object::default()));
+
+        let mut qr = document();
+        assert_eq!(
+            qr.set("address.tags[2].name", 1).unwrap_err(),
+            SurrealResponseError::IndexOutOfRange { index: 2, len: 0 }
+        );
+        assert_eq!(
+            qr.insert("tags[4]", 1).unwrap_err(),
+            SurrealResponseError::IndexOutOfRange { index: 4, len: 2 }
+        );
+        assert_eq!(
+            qr.push("name.first", 1).unwrap_err(),
+            SurrealResponseError::ExpectedAnObject
+        );
+        assert_eq!(qr.0, document().0);
+    }
+
+    #[test]
+    fn test_remove() {
+        let mut qr = document();
+        assert_eq!(qr.remove("tags[0]"), Ok(Some(Value::from("a"))));
+        assert_eq!(qr.remove("name"), Ok(Some(Value::from("john"))));
+        assert_eq!(qr.remove("name"), Ok(None));
+        assert_eq!(qr.remove("missing[0]"), Ok(None));
+        assert_eq!(
+            qr.get("tags").unwrap().0,
+            Value::from(vec![Value::from("b")])
+        );
+        assert!(qr.remove("").unwrap().is_some());
+        assert!(qr.is_none());
+    }
+
+    #[test]
+    fn test_insert_and_push() {
+        let mut qr = document();
+        qr.insert("tags[0]", "first".to_owned()).unwrap();
+        qr.insert("tags[-1]", "last".to_owned()).unwrap();
+        qr.push("tags", "pushed".to_owned()).unwrap();
+        qr.push("history.events", 1).unwrap();
+        assert_eq!(
+            qr.get("tags").unwrap().deserialize::<Vec<String>>(),
+            Ok(vec![
+                "first".to_owned(),
+                "a".to_owned(),
+                "b".to_owned(),
+                "last".to_owned(),
+                "pushed".to_owned()
+            ])
+        );
+        assert_eq!(
+            qr.get("history.events").unwrap().deserialize::<Vec<i64>>(),
+            Ok(vec![1])
+        );
+        assert_eq!(
+            qr.insert("tags[9]", 1).unwrap_err(),
+            SurrealResponseError::IndexOutOfRange { index: 9, len: 5 }
+        );
+        assert_eq!(
+            qr.push("name", 1).unwrap_err(),
+            SurrealResponseError::ExpectedAnArray
+        );
+    }
+}
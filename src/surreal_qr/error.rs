@@ -0,0 +1,411 @@
+use std::any::type_name;
+use std::collections::VecDeque;
+use std::fmt::{self, Display, Formatter};
+use std::sync::{Mutex, MutexGuard, PoisonError};
+
+use serde::{Deserialize, Serialize};
+use surrealdb::sql::Value;
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum SurrealResponseError {
+    ExpectedAnObject,
+    ExpectedAnArray,
+    ExpectedANumber,
+    ExpectedANumberI64,
+    ExpectedANumberF64,
+    ExpectedANumberDecimal,
+    ExpectedAStrand,
+    ExpectedAThing,
+    ExpectedADuration,
+    ExpectedADatetime,
+    ExpectedAGeometry,
+    ExpectedABool,
+    ExpectedAUuid,
+    ExpectedABytes,
+    ExpectedASet,
+    UnexpectedValueType,
+    OutOfRange,
+    CannotReadNoneValue,
+    ExpectedAnArrayWith1ItemToDeserializeToObject,
+    InvalidEnumFormat,
+    TypeEnumMustBeString,
+    UnknownVariant,
+    NumberOfFieldOfLengthOfDbValueNotMatchLengthOfEnum,
+    /// The number does not fit in the target integer type.
+    NumberOverflow,
+    /// A negative number was read into an unsigned integer type.
+    NumberSignMismatch,
+    /// A number with a fractional part was read into an integer type.
+    NumberPrecisionLoss,
+    /// A zero was read into a `NonZero` integer type.
+    ExpectedANonZeroNumber,
+    /// A string that is not exactly one character was read into a `char`.
+    ExpectedAChar,
+    /// An array did not have the exact number of items of a tuple or a fixed-size array.
+    ArrayLengthMismatch {
+        expected: usize,
+        found: usize,
+    },
+    /// A serde type could not be converted from or into a value.
+    SerdeError(String),
+    /// An index was outside of an array, negative indices count from the end.
+    IndexOutOfRange {
+        index: i64,
+        len: usize,
+    },
+    /// A path string does not follow the `users[0].address.city` syntax.
+    InvalidPath {
+        path: String,
+        message: String,
+    },
+    /// A path that selects several values was used where a single value is expected.
+    AmbiguousPath,
+    /// A string could not be parsed into the target type, with the message of the parser.
+    ParseError(String),
+    /// None of the types of an untagged union could be read, with the error of each of them.
+    NoMatchingVariant(Vec<SurrealResponseError>),
+    /// Wraps another error with the location it happened at and the types involved.
+    Context {
+        /// Location of the failing value, relative to the value being deserialized.
+        path: ErrorPath,
+        /// The underlying error, never a `Context` itself.
+        error: Box<SurrealResponseError>,
+        /// Kind of the value that was found, as reported by `Value::kindof`.
+        found: Option<String>,
+        /// Name of the rust type that was being deserialized.
+        type_name: Option<String>,
+    },
+}
+
+impl SurrealResponseError {
+    /// Attach the kind of `found` and the name of the target type `T` to this error.
+    pub fn mismatch<T: ?Sized>(self, found: &Value) -> Self {
+        match self {
+            Self::Context { path, error, .. } => Self::Context {
+                path,
+                error,
+                found: Some(found.kindof().to_owned()),
+                type_name: Some(type_name::<T>().to_owned()),
+            },
+            error => Self::Context {
+                path: ErrorPath::default(),
+                error: Box::new(error),
+                found: Some(found.kindof().to_owned()),
+                type_name: Some(type_name::<T>().to_owned()),
+            },
+        }
+    }
+
+    /// Prefix the path of this error with an object field.
+    pub fn at_field<S: Into<String>>(self, field: S) -> Self {
+        self.at(PathSegment::Field(field.into()))
+    }
+
+    /// Prefix the path of this error with an array index.
+    pub fn at_index(self, index: usize) -> Self {
+        self.at(PathSegment::Index(index))
+    }
+
+    /// Prefix the path of this error with every segment of `path`.
+    pub fn at_path(self, path: &ErrorPath) -> Self {
+        path.0
+            .iter()
+            .rev()
+            .cloned()
+            .fold(self, |error, segment| error.at(segment))
+    }
+
+    fn at(self, segment: PathSegment) -> Self {
+        match self {
+            Self::Context {
+                mut path,
+                error,
+                found,
+                type_name,
+            } => {
+                path.0.insert(0, segment);
+                Self::Context {
+                    path,
+                    error,
+                    found,
+                    type_name,
+                }
+            }
+            error => Self::Context {
+                path: ErrorPath(vec![segment]),
+                error: Box::new(error),
+                found: None,
+                type_name: None,
+            },
+        }
+    }
+
+    /// The underlying error, without any context attached.
+    pub fn kind(&self) -> &SurrealResponseError {
+        match self {
+            Self::Context { error, .. } => error.kind(),
+            error => error,
+        }
+    }
+
+    /// The location of the failing value, if known.
+    pub fn path(&self) -> Option<&ErrorPath> {
+        match self {
+            Self::Context { path, .. } => Some(path),
+            _ => None,
+        }
+    }
+}
+
+impl Display for SurrealResponseError {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        match self {
+            Self::ExpectedAnObject => write!(f, "expected an object"),
+            Self::ExpectedAnArray => write!(f, "expected an array"),
+            Self::ExpectedANumber => write!(f, "expected a number"),
+            Self::ExpectedANumberI64 => write!(f, "expected an integer number"),
+            Self::ExpectedANumberF64 => write!(f, "expected a float number"),
+            Self::ExpectedANumberDecimal => write!(f, "expected a decimal number"),
+            Self::ExpectedAStrand => write!(f, "expected a string"),
+            Self::ExpectedAThing => write!(f, "expected a record id"),
+            Self::ExpectedADuration => write!(f, "expected a duration"),
+            Self::ExpectedADatetime => write!(f, "expected a datetime"),
+            Self::ExpectedAGeometry => write!(f, "expected a geometry"),
+            Self::ExpectedABool => write!(f, "expected a bool"),
+            Self::ExpectedAUuid => write!(f, "expected a uuid"),
+            Self::ExpectedABytes => write!(f, "expected bytes"),
+            Self::ExpectedASet => write!(f, "expected a set of unique items"),
+            Self::UnexpectedValueType => write!(f, "unexpected value type"),
+            Self::OutOfRange => write!(f, "out of range"),
+            Self::CannotReadNoneValue => write!(f, "cannot read a none value"),
+            Self::ExpectedAnArrayWith1ItemToDeserializeToObject => {
+                write!(
+                    f,
+                    "expected an array with exactly 1 item to deserialize to an object"
+                )
+            }
+            Self::InvalidEnumFormat => write!(f, "invalid enum format"),
+            Self::TypeEnumMustBeString => write!(f, "the enum variant must be a string"),
+            Self::UnknownVariant => write!(f, "unknown enum variant"),
+            Self::NumberOfFieldOfLengthOfDbValueNotMatchLengthOfEnum => write!(
+                f,
+                "the number of fields in the value does not match the enum variant"
+            ),
+            Self::NumberOverflow => write!(f, "the number does not fit in the target type"),
+            Self::NumberSignMismatch => {
+                write!(f, "a negative number cannot be read into an unsigned type")
+            }
+            Self::NumberPrecisionLoss => {
+                write!(
+                    f,
+                    "a number with a fractional part cannot be read into an integer"
+                )
+            }
+            Self::ExpectedANonZeroNumber => write!(f, "expected a non-zero number"),
+            Self::ExpectedAChar => write!(f, "expected a string of exactly one character"),
+            Self::ArrayLengthMismatch { expected, found } => write!(
+                f,
+                "expected an array of {} items, found {} items",
+                expected, found
+            ),
+            Self::SerdeError(message) => write!(f, "serde conversion failed: {}", message),
+            Self::IndexOutOfRange { index, len } => write!(
+                f,
+                "the index {} is out of range for an array of {} items",
+                index, len
+            ),
+            Self::InvalidPath { path, message } => {
+                write!(f, "invalid path `{}`: {}", path, message)
+            }
+            Self::AmbiguousPath => write!(f, "the path selects several values instead of one"),
+            Self::ParseError(message) => write!(f, "failed to parse the string: {}", message),
+            Self::NoMatchingVariant(errors) => {
+                write!(f, "none of the variants matched")?;
+                for (i, error) in errors.iter().enumerate() {
+                    let separator = if i == 0 { ": " } else { "; " };
+                    write!(f, "{}{}", separator, error.full_message())?;
+                }
+
+                Ok(())
+            }
+            Self::Context {
+                path,
+                found,
+                type_name,
+                ..
+            } => {
+                write!(f, "failed to deserialize")?;
+                if let Some(type_name) = type_name {
+                    write!(f, " `{}`", type_name)?;
+                }
+                if !path.is_empty() {
+                    write!(f, " at `{}`", path)?;
+                }
+                if let Some(found) = found {
+                    write!(f, ", found {}", found)?;
+                }
+
+                Ok(())
+            }
+        }
+    }
+}
+
+impl std::error::Error for SurrealResponseError {
+    fn source(&self) -> Option<&(dyn std::error::Error + 'static)> {
+        match self {
+            Self::Context { error, .. } => Some(error.as_ref()),
+            _ => None,
+        }
+    }
+}
+
+impl SurrealResponseError {
+    /// The message of this error followed by the message of its source, if any.
+    pub fn full_message(&self) -> String {
+        match self {
+            Self::Context { error, .. } => format!("{}: {}", self, error.full_message()),
+            _ => self.to_string(),
+        }
+    }
+
+    /// Recover the structured error from a `surrealdb::Error` that was converted from it.
+    ///
+    /// The conversion only keeps the readable message in the `surrealdb::Error`, so the structured
+    /// errors are remembered by message, for the last `CONVERTED_ERRORS` conversions.
+    pub fn from_surreal_error(error: &surrealdb::Error) -> Option<Self> {
+        match error {
+            surrealdb::Error::Api(surrealdb::error::Api::ParseError(message)) => converted_errors()
+                .iter()
+                .rev()
+                .find(|(converted, _)| converted == message)
+                .map(|(_, error)| error.clone()),
+            _ => None,
+        }
+    }
+}
+
+/// How many converted errors `from_surreal_error` can recover.
+const CONVERTED_ERRORS: usize = 64;
+
+fn converted_errors() -> MutexGuard<'static, VecDeque<(String, SurrealResponseError)>> {
+    static ERRORS: Mutex<VecDeque<(String, SurrealResponseError)>> = Mutex::new(VecDeque::new());
+    ERRORS.lock().unwrap_or_else(PoisonError::into_inner)
+}
+
+/// Converts into a `ParseError` holding the readable message of the error, which
+/// `from_surreal_error` maps back to the structured error.
+impl From<SurrealResponseError> for surrealdb::error::Api {
+    fn from(value: SurrealResponseError) -> Self {
+        let message = value.full_message();
+        let mut errors = converted_errors();
+        if errors.len() == CONVERTED_ERRORS {
+            errors.pop_front();
+        }
+        errors.push_back((message.clone(), value));
+
+        Self::ParseError(message)
+    }
+}
+
+impl From<SurrealResponseError> for surrealdb::Error {
+    fn from(value: SurrealResponseError) -> Self {
+        Self::Api(value.into())
+    }
+}
+
+#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
+pub enum PathSegment {
+    Field(String),
+    Index(usize),
+    /// An index counting from the end of an array, displayed as `[-n]`.
+    FromEnd(usize),
+    /// A segment displayed as it was written, such as `[*]` or `[?status="paid"]`.
+    Raw(String),
+}
+
+/// A location inside a value, displayed as `user.addresses[2].zip`.
+#[derive(Debug, Clone, Default, Serialize, Deserialize, PartialEq)]
+pub struct ErrorPath(pub Vec<PathSegment>);
+
+impl ErrorPath {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl Display for ErrorPath {
+    fn fmt(&self, f: &mut Formatter<'_>) -> fmt::Result {
+        for (i, segment) in self.0.iter().enumerate() {
+            match segment {
+                PathSegment::Field(field) if i == 0 => write!(f, "{}", field)?,
+                PathSegment::Field(field) => write!(f, ".{}", field)?,
+                PathSegment::Index(index) => write!(f, "[{}]", index)?,
+                PathSegment::FromEnd(n) => write!(f, "[-{}]", n)?,
+                PathSegment::Raw(raw) => write!(f, "{}", raw)?,
+            }
+        }
+
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    #[test]
+    fn test_path_is_prefixed_from_leaf_to_root() {
+        let error = SurrealResponseError::ExpectedAStrand
+            .mismatch::<String>(&Value::from(1))
+            .at_field("zip")
+            .at_index(2)
+            .at_field("addresses")
+            .at_field("user");
+
+        assert_eq!(error.path().unwrap().to_string(), "user.addresses[2].zip");
+        assert_eq!(error.kind(), &SurrealResponseError::ExpectedAStrand);
+        match error {
+            SurrealResponseError::Context {
+                found, type_name, ..
+            } => {
+                assert_eq!(found.as_deref(), Some("int"));
+                assert_eq!(type_name.as_deref(), Some("alloc::string::String"));
+            }
+            _ => panic!("Expected a context error"),
+        }
+    }
+
+    #[test]
+    fn test_display_and_source() {
+        let error = SurrealResponseError::ExpectedAStrand
+            .mismatch::<String>(&Value::from(1))
+            .at_field("name");
+
+        assert_eq!(
+            error.to_string(),
+            "failed to deserialize `alloc::string::String` at `name`, found int"
+        );
+        assert_eq!(
+            std::error::Error::source(&error).unwrap().to_string(),
+            "expected a string"
+        );
+    }
+
+    #[test]
+    fn test_surreal_error_preserves_structure() {
+        let error = SurrealResponseError::ExpectedAUuid
+            .mismatch::<uuid::Uuid>(&Value::from("id"))
+            .at_index(3);
+
+        let surreal_error: surrealdb::Error = error.clone().into();
+        assert_eq!(
+            surreal_error.to_string(),
+            "Parse error: failed to deserialize `uuid::Uuid` at `[3]`, found string: expected a uuid"
+        );
+        assert_eq!(
+            SurrealResponseError::from_surreal_error(&surreal_error),
+            Some(error)
+        );
+    }
+}
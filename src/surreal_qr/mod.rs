@@ -12,52 +12,42 @@ use surrealdb::Response as QueryResponse;
 
 use crate::proxy::default::SurrealDeserializer;
 
-#[derive(Debug, Serialize, Deserialize, Clone)]
-pub struct SurrealQR(pub Value);
+mod edit;
+mod error;
+mod path;
+mod qr_ref;
 
-#[derive(Debug, Clone, Serialize, Deserialize, PartialEq)]
-pub enum SurrealResponseError {
-    ExpectedAnObject,
-    ExpectedAnArray,
-    ExpectedANumber,
-    ExpectedANumberI64,
-    ExpectedANumberF64,
-    ExpectedANumberDecimal,
-    ExpectedAStrand,
-    ExpectedAThing,
-    ExpectedADuration,
-    ExpectedADatetime,
-    ExpectedAGeometry,
-    ExpectedABool,
-    ExpectedAUuid,
-    ExpectedABytes,
-    ExpectedASet,
-    UnexpectedValueType,
-    OutOfRange,
-    CannotReadNoneValue,
-    ExpectedAnArrayWith1ItemToDeserializeToObject,
-    InvalidEnumFormat,
-    TypeEnumMustBeString,
-    UnknownVariant,
-    NumberOfFieldOfLengthOfDbValueNotMatchLengthOfEnum,
-}
+pub use error::{ErrorPath, PathSegment, SurrealResponseError};
+use path::Segment;
+pub use qr_ref::SurrealQRRef;
 
-impl From<SurrealResponseError> for surrealdb::error::Api {
-    fn from(value: SurrealResponseError) -> Self {
-        Self::ParseError(format!("{:?}", value))
-    }
-}
-
-impl From<SurrealResponseError> for surrealdb::Error {
-    fn from(value: SurrealResponseError) -> Self {
-        Self::Api(surrealdb::error::Api::ParseError(format!("{:?}", value)))
-    }
-}
+#[derive(Debug, Serialize, Deserialize, Clone)]
+pub struct SurrealQR(pub Value);
 
 pub enum RPath<'a> {
     Index(usize),
+    /// The last item of an array.
+    Last,
+    /// The n-th item from the end of an array, `FromEnd(1)` being the last one.
+    FromEnd(usize),
     Field(&'a str),
     Chain(Vec<RPath<'a>>),
+    /// A path in the string syntax, e.g. `users[0].address.city`, parsed when it is used.
+    Path(&'a str),
+    /// Every item of an array, `[*]`.
+    All,
+    /// The items of an array from `start` up to, but excluding, `end`, `[1..5]`.
+    /// Bounds past the end of the array are clamped to it.
+    Slice {
+        start: Option<usize>,
+        end: Option<usize>,
+    },
+    /// The items of an array whose value at the path `field` equals `equals`,
+    /// `[?status="paid"]`.
+    Filter {
+        field: &'a str,
+        equals: Value,
+    },
 }
 
 impl<'a> RPath<'a> {
@@ -78,40 +68,254 @@ impl<'a> RPath<'a> {
                 chain.push(path);
                 Self::Chain(chain)
             }
-            Self::Index(index) => {
-                let mut chain: Vec<RPath<'a>> = vec![];
-                chain.push(RPath::Index(index));
-                chain.push(path);
-                Self::Chain(chain)
+            path_item => Self::Chain(vec![path_item, path]),
+        }
+    }
+
+    /// The location this path points to, to be recorded in an error.
+    fn error_path(&self) -> ErrorPath {
+        let mut segments = Vec::new();
+        self.push_error_segments(&mut segments);
+        ErrorPath(segments)
+    }
+
+    fn push_error_segments(&self, segments: &mut Vec<PathSegment>) {
+        match self {
+            Self::Index(index) => segments.push(PathSegment::Index(*index)),
+            Self::Last => segments.push(PathSegment::FromEnd(1)),
+            Self::FromEnd(n) => segments.push(PathSegment::FromEnd(*n)),
+            Self::Field(field) => segments.push(PathSegment::Field((*field).to_owned())),
+            Self::Chain(chain) => chain
+                .iter()
+                .for_each(|path| path.push_error_segments(segments)),
+            Self::Path(path) => match path::parse(path) {
+                Ok(parsed) => parsed
+                    .iter()
+                    .for_each(|segment| segment.as_path().push_error_segments(segments)),
+                Err(_) => segments.push(PathSegment::Raw((*path).to_owned())),
+            },
+            Self::All => segments.push(PathSegment::Raw("[*]".to_owned())),
+            Self::Slice { start, end } => {
+                let bound =
+                    |bound: &Option<usize>| bound.map(|b| b.to_string()).unwrap_or_default();
+                segments.push(PathSegment::Raw(format!(
+                    "[{}..{}]",
+                    bound(start),
+                    bound(end)
+                )))
             }
-            Self::Field(field) => {
-                let mut chain: Vec<RPath<'a>> = vec![];
-                chain.push(RPath::Field(field));
-                chain.push(path);
-                Self::Chain(chain)
+            Self::Filter { field, equals } => {
+                segments.push(PathSegment::Raw(format!("[?{}={}]", field, equals)))
             }
         }
     }
 }
 
-impl<'a> Into<RPath<'a>> for &'a str {
-    fn into(self) -> RPath<'a> {
-        RPath::Field(self)
+/// A string is read in the path syntax, use `RPath::Field` for a key that contains `.`, `[` or `\`.
+///
+/// Strings used to always be a single field, see the README for the keys this changes.
+impl<'a> From<&'a str> for RPath<'a> {
+    fn from(value: &'a str) -> Self {
+        RPath::Path(value)
+    }
+}
+
+impl From<usize> for RPath<'static> {
+    fn from(value: usize) -> Self {
+        RPath::Index(value)
+    }
+}
+
+/// Negative indices count from the end of the array, `-1` being the last item.
+impl From<isize> for RPath<'static> {
+    fn from(value: isize) -> Self {
+        if value < 0 {
+            RPath::FromEnd(value.unsigned_abs())
+        } else {
+            RPath::Index(value as usize)
+        }
     }
 }
 
-impl Into<RPath<'static>> for usize {
-    fn into(self) -> RPath<'static> {
-        RPath::Index(self)
+impl From<i32> for RPath<'static> {
+    fn from(value: i32) -> Self {
+        RPath::from(value as isize)
     }
 }
 
-impl<'a, T> Into<RPath<'a>> for Vec<T>
+impl<'a, T> From<Vec<T>> for RPath<'a>
 where
     T: Into<RPath<'a>>,
 {
-    fn into(self) -> RPath<'a> {
-        RPath::Chain(self.into_iter().map(|it| it.into()).collect())
+    fn from(value: Vec<T>) -> Self {
+        RPath::Chain(value.into_iter().map(|it| it.into()).collect())
+    }
+}
+
+/// Read `value` as an object, an array with a single object being read as that object.
+fn object_of(value: &Value) -> Result<Option<&Object>, SurrealResponseError> {
+    match value {
+        Value::None => Ok(None),
+        Value::Object(value) => Ok(Some(value)),
+        Value::Array(ref value) => {
+            if value.is_empty() {
+                return Ok(None);
+            }
+
+            if value.len() != 1 {
+                return Err(SurrealResponseError::ExpectedAnArrayWith1ItemToDeserializeToObject);
+            }
+
+            if let Some(Value::Object(ref obj)) = value.0.first() {
+                return Ok(Some(obj));
+            }
+
+            Err(SurrealResponseError::ExpectedAnObject)
+        }
+        _ => Err(SurrealResponseError::ExpectedAnObject),
+    }
+}
+
+fn array_of(value: &Value) -> Result<Option<&Array>, SurrealResponseError> {
+    match value {
+        Value::None => Ok(None),
+        Value::Array(ref value) => Ok(Some(value)),
+        _ => Err(SurrealResponseError::ExpectedAnArray),
+    }
+}
+
+/// Borrow the item at `index` of an array, counting from the end when `index` is negative.
+fn item_at(value: &Value, index: i64) -> Result<Option<&Value>, SurrealResponseError> {
+    let Some(array) = array_of(value)? else {
+        return Ok(None);
+    };
+
+    Ok(Some(&array[position(index, array.len())?]))
+}
+
+/// The position of `index` in an array of `len` items, counting from the end when negative.
+fn position(index: i64, len: usize) -> Result<usize, SurrealResponseError> {
+    let position = if index < 0 {
+        usize::try_from(index.unsigned_abs())
+            .ok()
+            .and_then(|n| len.checked_sub(n))
+    } else {
+        usize::try_from(index).ok().filter(|i| *i < len)
+    };
+
+    position.ok_or(SurrealResponseError::IndexOutOfRange { index, len })
+}
+
+/// The signed index of an `Index`, `Last` or `FromEnd` path, negative when counting from the end.
+///
+/// An index past `i64::MAX` saturates, so it is out of range of every array. `FromEnd(0)` points
+/// to no item and is rejected.
+fn signed_index(path: &RPath) -> Result<i64, SurrealResponseError> {
+    match path {
+        RPath::Index(index) => Ok(i64::try_from(*index).unwrap_or(i64::MAX)),
+        RPath::FromEnd(0) => Err(SurrealResponseError::InvalidPath {
+            path: "[-0]".to_owned(),
+            message: "an index from the end starts at 1".to_owned(),
+        }),
+        RPath::FromEnd(n) => Ok(i64::try_from(*n).map_or(i64::MIN, |n| -n)),
+        _ => Ok(-1),
+    }
+}
+
+/// The values a path points to, several of them once the path went through an `All`, a `Slice`
+/// or a `Filter`.
+enum Selection<'v> {
+    One(Option<&'v Value>),
+    Many(Vec<&'v Value>),
+}
+
+impl Selection<'_> {
+    /// The selected value, or an array of the selected values.
+    fn into_value(self) -> Value {
+        match self {
+            Selection::One(value) => value.cloned().unwrap_or(Value::None),
+            Selection::Many(values) => Value::from(values.into_iter().cloned().collect::<Vec<_>>()),
+        }
+    }
+}
+
+/// Walk `path` from `value` without cloning, a missing field or a none value giving `None`.
+///
+/// The values selected by several items are flattened into a single list, skipping the
+/// items where the rest of the path is missing or does not apply, such as an index out of the
+/// range of the item or a field of an item that is not an object.
+fn lookup<'v>(value: &'v Value, path: &RPath) -> Result<Selection<'v>, SurrealResponseError> {
+    if let RPath::Path(path) = path {
+        let segments = path::parse(path)?;
+        return lookup(
+            value,
+            &RPath::Chain(segments.iter().map(Segment::as_path).collect()),
+        );
+    }
+
+    if value.is_none_or_null() {
+        return Ok(Selection::One(None));
+    }
+
+    match path {
+        RPath::Field(field) => {
+            let Some(object) = object_of(value)? else {
+                return Ok(Selection::One(None));
+            };
+
+            Ok(Selection::One(object.get(*field)))
+        }
+        RPath::Chain(chain) => chain.iter().try_fold(
+            Selection::One(Some(value)),
+            |selection, path| match selection {
+                Selection::One(Some(value)) => lookup(value, path),
+                Selection::One(None) => Ok(Selection::One(None)),
+                Selection::Many(values) => {
+                    let mut selected = Vec::new();
+                    for value in values {
+                        match lookup(value, path) {
+                            Ok(Selection::One(value)) => selected.extend(value),
+                            Ok(Selection::Many(values)) => selected.extend(values),
+                            Err(e @ SurrealResponseError::InvalidPath { .. }) => return Err(e),
+                            Err(_) => {}
+                        }
+                    }
+
+                    Ok(Selection::Many(selected))
+                }
+            },
+        ),
+        RPath::Index(_) | RPath::Last | RPath::FromEnd(_) => {
+            Ok(Selection::One(item_at(value, signed_index(path)?)?))
+        }
+        RPath::All | RPath::Slice { .. } | RPath::Filter { .. } => {
+            let Some(array) = array_of(value)? else {
+                return Ok(Selection::One(None));
+            };
+
+            let items = match path {
+                RPath::Slice { start, end } => {
+                    let end = end.unwrap_or(array.len()).min(array.len());
+                    let start = start.unwrap_or(0).min(end);
+                    &array[start..end]
+                }
+                _ => &array[..],
+            };
+
+            Ok(Selection::Many(
+                items
+                    .iter()
+                    .filter(|item| match path {
+                        RPath::Filter { field, equals } => matches!(
+                            lookup(item, &RPath::Path(field)),
+                            Ok(Selection::One(Some(found))) if found == equals
+                        ),
+                        _ => true,
+                    })
+                    .collect(),
+            ))
+        }
+        RPath::Path(_) => unreachable!("paths are parsed above"),
     }
 }
 
@@ -125,86 +329,27 @@ impl SurrealQR {
     }
 
     pub fn is_none(&self) -> bool {
-        return self.0.is_none_or_null();
+        self.0.is_none_or_null()
     }
 
     pub fn get<'a, T>(&self, path: T) -> Result<Self, SurrealResponseError>
     where
         T: Into<RPath<'a>> + Sized,
     {
-        let path: RPath = path.into();
-        if self.is_none() {
-            return Ok(Self(Value::None));
-        }
-
-        match path {
-            RPath::Index(index) => {
-                let array = self.array()?;
-                if None == array {
-                    Ok(Self(Value::None))
-                } else {
-                    let mut array = array.unwrap().to_owned();
-                    if array.len() - 1 < index as usize {
-                        return Err(SurrealResponseError::OutOfRange);
-                    }
-
-                    let value = array.remove(index as usize);
-                    Ok(Self(value))
-                }
-            }
-            RPath::Field(field) => {
-                let object = self.object()?;
-                if None == object {
-                    Ok(Self(Value::None))
-                } else {
-                    let object = object.unwrap();
-                    let value = object.get(field);
-                    if None == value {
-                        Ok(Self(Value::None))
-                    } else {
-                        Ok(Self(value.unwrap().to_owned()))
-                    }
-                }
-            }
-            RPath::Chain(mut chain) => {
-                if chain.is_empty() {
-                    return Ok(self.to_owned());
-                }
+        Ok(Self(lookup(&self.0, &path.into())?.into_value()))
+    }
 
-                self.get(chain.remove(0))?.get(chain)
-            }
-        }
+    /// A borrowed view of this value, to navigate it without cloning.
+    pub fn view(&self) -> SurrealQRRef<'_> {
+        SurrealQRRef(&self.0)
     }
 
     pub fn object(&self) -> Result<Option<&Object>, SurrealResponseError> {
-        match &self.0 {
-            Value::None => Ok(None),
-            Value::Object(value) => Ok(Some(value)),
-            Value::Array(ref value) => {
-                if value.is_empty() {
-                    return Ok(None);
-                }
-
-                if value.len() != 1 {
-                    return Err(SurrealResponseError::ExpectedAnArrayWith1ItemToDeserializeToObject);
-                }
-
-                if let Some(Value::Object(ref obj)) = value.0.first() {
-                    return Ok(Some(obj));
-                }
-
-                Err(SurrealResponseError::ExpectedAnObject)
-            }
-            _ => Err(SurrealResponseError::ExpectedAnObject),
-        }
+        object_of(&self.0)
     }
 
     pub fn array(&self) -> Result<Option<&Array>, SurrealResponseError> {
-        match &self.0 {
-            Value::None => Ok(None),
-            Value::Array(ref value) => Ok(Some(value)),
-            _ => Err(SurrealResponseError::ExpectedAnArray),
-        }
+        array_of(&self.0)
     }
 
     pub fn deserialize<T>(&self) -> Result<T, SurrealResponseError>
@@ -214,6 +359,33 @@ impl SurrealQR {
         SurrealDeserializer::deserialize(&self.0)
     }
 
+    /// Deserialize the value at `path`, recording the path in the error.
+    pub fn get_as<'a, T, P>(&self, path: P) -> Result<T, SurrealResponseError>
+    where
+        T: SurrealDeserializer,
+        P: Into<RPath<'a>>,
+    {
+        self.view().get_as(path)
+    }
+
+    /// Deserialize the value at `path`, `None` when it is missing or null.
+    pub fn get_opt<'a, T, P>(&self, path: P) -> Result<Option<T>, SurrealResponseError>
+    where
+        T: SurrealDeserializer,
+        P: Into<RPath<'a>>,
+    {
+        self.view().get_opt(path)
+    }
+
+    /// Deserialize the value at `path`, the default of `T` when it is missing or null.
+    pub fn get_or_default<'a, T, P>(&self, path: P) -> Result<T, SurrealResponseError>
+    where
+        T: SurrealDeserializer + Default,
+        P: Into<RPath<'a>>,
+    {
+        self.view().get_or_default(path)
+    }
+
     pub fn number(self) -> Result<Option<Number>, SurrealResponseError> {
         match self.0 {
             Value::None => Ok(None),
@@ -318,9 +490,9 @@ impl DerefMut for SurrealQR {
     }
 }
 
-impl Into<Value> for SurrealQR {
-    fn into(self) -> Value {
-        self.0
+impl From<SurrealQR> for Value {
+    fn from(value: SurrealQR) -> Self {
+        value.0
     }
 }
 
@@ -341,8 +513,10 @@ where
         }
 
         let mut result = Vec::new();
-        for item in arr.take().unwrap().iter() {
-            result.push(SurrealDeserializer::deserialize(&item)?);
+        for (index, item) in arr.take().unwrap().iter().enumerate() {
+            result.push(
+                <T as SurrealDeserializer>::deserialize(item).map_err(|e| e.at_index(index))?,
+            );
         }
 
         Ok(result)
@@ -366,7 +540,7 @@ where
             }
         }
 
-        return Ok(value.deserialize()?);
+        Ok(value.deserialize()?)
     }
 }
 
@@ -377,6 +551,24 @@ impl<'a> QueryResult<SurrealQR> for RPath<'a> {
                 let value: surrealdb::Value = response.take(index)?;
                 Ok(SurrealQR(value.into_inner()))
             }
+            Self::Last | Self::FromEnd(_) => {
+                let value: surrealdb::Value =
+                    response.take(statement_from_end(response, &self)?)?;
+                Ok(SurrealQR(value.into_inner()))
+            }
+            // A path string reads like the chain of its segments, so a leading index selects
+            // the statement.
+            Self::Path(path) => {
+                let segments = path::parse(path)?;
+                if segments.is_empty() {
+                    let value: surrealdb::Value = response.take(0)?;
+                    return Ok(SurrealQR(value.into_inner()));
+                }
+
+                response.take(RPath::Chain(
+                    segments.iter().map(Segment::as_path).collect(),
+                ))
+            }
             Self::Chain(mut paths) => {
                 if paths.is_empty() {
                     return Err(surrealdb::error::Api::ParseError(
@@ -386,31 +578,273 @@ impl<'a> QueryResult<SurrealQR> for RPath<'a> {
                 }
 
                 let value: SurrealQR = match paths.remove(0) {
-                    Self::Index(index) => {
-                        let value: surrealdb::Value = response.take(index)?;
-                        let core_value: Value = value.into_inner();
-                        SurrealQR(core_value)
-                    }
-                    Self::Field(str) => {
-                        let value: surrealdb::Value = response.take(0)?;
-                        let core_value: Value = value.into_inner();
-                        let value = SurrealQR(core_value);
-                        value.get(str)?
-                    }
                     Self::Chain(paths) => {
                         let value: surrealdb::Value = response.take(0)?;
                         let core_value: Value = value.into_inner();
                         let value = SurrealQR(core_value);
                         value.get(paths)?
                     }
+                    path => response.take(path)?,
                 };
 
                 Ok(value.get(RPath::Chain(paths))?)
             }
-            Self::Field(str) => {
+            path @ (Self::Field(_) | Self::All | Self::Slice { .. } | Self::Filter { .. }) => {
                 let value: surrealdb::Value = response.take(0)?;
-                return Ok(SurrealQR(value.into_inner()).get(RPath::from(str))?);
+                Ok(SurrealQR(value.into_inner()).get(path)?)
             }
         }
     }
 }
+
+/// The index of the statement that a `Last` or `FromEnd` path refers to, counting only the
+/// statements whose result has not been taken yet.
+fn statement_from_end(
+    response: &QueryResponse,
+    path: &RPath,
+) -> Result<usize, SurrealResponseError> {
+    let index = signed_index(path)?;
+    let len = response.num_statements();
+    let out_of_range = SurrealResponseError::IndexOutOfRange { index, len };
+    let position = usize::try_from(index.unsigned_abs())
+        .ok()
+        .and_then(|n| len.checked_sub(n))
+        .ok_or(out_of_range.clone())?;
+
+    // The results keep the index of their statement and taken ones are removed, so the
+    // remaining indexes are found by looking them up in order.
+    #[allow(deprecated)]
+    let remaining = (0..usize::MAX)
+        .filter(|statement| QueryResult::<surrealdb::Value>::stats(statement, response).is_some());
+
+    remaining.take(len).nth(position).ok_or(out_of_range)
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn numbers() -> SurrealQR {
+        SurrealQR(Value::from(vec![
+            Value::from(1),
+            Value::from(2),
+            Value::from(3),
+        ]))
+    }
+
+    #[test]
+    fn test_get_index() {
+        let qr = numbers();
+        assert_eq!(qr.get(0).unwrap().0, Value::from(1));
+        assert_eq!(qr.get(RPath::Last).unwrap().0, Value::from(3));
+        assert_eq!(qr.get(-3).unwrap().0, Value::from(1));
+        assert_eq!(qr.get(RPath::FromEnd(2)).unwrap().0, Value::from(2));
+
+        assert_eq!(
+            qr.get(3).unwrap_err(),
+            SurrealResponseError::IndexOutOfRange { index: 3, len: 3 }
+        );
+        assert_eq!(
+            qr.get(usize::MAX).unwrap_err(),
+            SurrealResponseError::IndexOutOfRange {
+                index: i64::MAX,
+                len: 3
+            }
+        );
+        assert!(matches!(
+            qr.get(RPath::FromEnd(0)).unwrap_err(),
+            SurrealResponseError::InvalidPath { .. }
+        ));
+        assert_eq!(
+            qr.get(-4).unwrap_err(),
+            SurrealResponseError::IndexOutOfRange { index: -4, len: 3 }
+        );
+        assert_eq!(
+            SurrealQR(Value::from(Vec::<Value>::new()))
+                .get(0)
+                .unwrap_err(),
+            SurrealResponseError::IndexOutOfRange { index: 0, len: 0 }
+        );
+        assert_eq!(
+            SurrealQR(Value::from(Vec::<Value>::new()))
+                .get(RPath::Last)
+                .unwrap_err(),
+            SurrealResponseError::IndexOutOfRange { index: -1, len: 0 }
+        );
+    }
+
+    #[test]
+    fn test_get_chain() {
+        let qr = SurrealQR(Value::Object(Object::from(
+            std::collections::BTreeMap::from([("items".to_owned(), numbers().0)]),
+        )));
+
+        assert_eq!(
+            qr.get(RPath::from("items").get(-1)).unwrap().0,
+            Value::from(3)
+        );
+        assert!(qr.get(RPath::from("missing").get(0)).unwrap().is_none());
+        assert!(qr.get(Vec::<RPath>::new()).unwrap().0 == qr.0);
+    }
+
+    fn orders() -> SurrealQR {
+        let order = |status: &str, total: i64| {
+            Value::Object(Object::from(std::collections::BTreeMap::from([
+                ("status".to_owned(), Value::from(status)),
+                ("total".to_owned(), Value::from(total)),
+            ])))
+        };
+
+        SurrealQR(Value::Object(Object::from(
+            std::collections::BTreeMap::from([(
+                "orders".to_owned(),
+                Value::from(vec![
+                    order("paid", 10),
+                    order("open", 20),
+                    order("paid", 30),
+                ]),
+            )]),
+        )))
+    }
+
+    #[test]
+    fn test_get_projections() {
+        let qr = orders();
+        let totals = |values: &[i64]| {
+            Value::from(values.iter().map(|v| Value::from(*v)).collect::<Vec<_>>())
+        };
+
+        assert_eq!(qr.get("orders[*].total").unwrap().0, totals(&[10, 20, 30]));
+        assert_eq!(qr.get("orders[1..].total").unwrap().0, totals(&[20, 30]));
+        assert_eq!(
+            qr.get("orders[..9].total").unwrap().0,
+            totals(&[10, 20, 30])
+        );
+        assert_eq!(
+            qr.get(r#"orders[?status="paid"].total"#).unwrap().0,
+            totals(&[10, 30])
+        );
+        assert_eq!(
+            qr.get("orders[?total=20].status").unwrap().0,
+            Value::from(vec![Value::from("open")])
+        );
+        assert_eq!(
+            qr.get(RPath::from("orders").get(RPath::Filter {
+                field: "status",
+                equals: Value::from("none")
+            }))
+            .unwrap()
+            .0,
+            totals(&[])
+        );
+        assert_eq!(qr.get("orders[*].missing").unwrap().0, totals(&[]));
+        assert_eq!(qr.get("orders[*].total[0]").unwrap().0, totals(&[]));
+        assert_eq!(qr.get("orders[*].total.value").unwrap().0, totals(&[]));
+        assert!(matches!(
+            qr.get(RPath::from("orders").get(RPath::All).get(RPath::FromEnd(0))),
+            Err(SurrealResponseError::InvalidPath { .. })
+        ));
+
+        let items = SurrealQR(Value::from(vec![
+            Value::from(vec![Value::from(1), Value::from(2)]),
+            Value::from(Vec::<Value>::new()),
+            Value::from(vec![Value::from(3)]),
+        ]));
+        assert_eq!(items.get("[*][0]").unwrap().0, totals(&[1, 3]));
+        assert_eq!(
+            qr.get("orders[*].total").unwrap().deserialize::<Vec<i64>>(),
+            Ok(vec![10, 20, 30])
+        );
+    }
+
+    #[test]
+    fn test_typed_accessors() {
+        let qr = orders();
+        assert_eq!(qr.get_as::<i64, _>("orders[-1].total"), Ok(30));
+        assert_eq!(
+            qr.get_as::<Vec<i64>, _>(r#"orders[?status="paid"].total"#),
+            Ok(vec![10, 30])
+        );
+        assert_eq!(
+            qr.get_opt::<String, _>("orders[0].status"),
+            Ok(Some("paid".to_owned()))
+        );
+        assert_eq!(qr.get_opt::<String, _>("orders[0].note"), Ok(None));
+        assert_eq!(qr.get_or_default::<Vec<String>, _>("tags"), Ok(vec![]));
+
+        let error = qr.get_as::<i64, _>("orders[1].status").unwrap_err();
+        assert_eq!(error.kind(), &SurrealResponseError::ExpectedANumberI64);
+        assert_eq!(error.path().unwrap().to_string(), "orders[1].status");
+
+        let error = qr.get_as::<Vec<String>, _>("orders[*].total").unwrap_err();
+        assert_eq!(error.path().unwrap().to_string(), "orders[*].total[0]");
+
+        let error = qr
+            .get_as::<i64, _>(RPath::from("orders").get(RPath::Last).get("x"))
+            .unwrap_err();
+        assert_eq!(error.path().unwrap().to_string(), "orders[-1].x");
+
+        let error = qr.get_as::<i64, _>("orders[7]").unwrap_err();
+        assert_eq!(
+            error.kind(),
+            &SurrealResponseError::IndexOutOfRange { index: 7, len: 3 }
+        );
+        assert_eq!(error.path().unwrap().to_string(), "orders[7]");
+    }
+
+    #[test]
+    fn test_get_path_string() {
+        let qr = SurrealQR(Value::Object(Object::from(
+            std::collections::BTreeMap::from([
+                ("items".to_owned(), numbers().0),
+                ("a.b".to_owned(), Value::from("dotted")),
+            ]),
+        )));
+
+        assert_eq!(qr.get("items[-1]").unwrap().0, Value::from(3));
+        assert_eq!(qr.get(r"a\.b").unwrap().0, Value::from("dotted"));
+        assert_eq!(
+            qr.get(RPath::Field("a.b")).unwrap().0,
+            Value::from("dotted")
+        );
+        assert_eq!(qr.get("").unwrap().0, qr.0);
+        assert!(matches!(
+            qr.get("items[").unwrap_err(),
+            SurrealResponseError::InvalidPath { .. }
+        ));
+        assert!(matches!(
+            SurrealQR(Value::None).get("items..").unwrap_err(),
+            SurrealResponseError::InvalidPath { .. }
+        ));
+    }
+
+    async fn three_statements() -> QueryResponse {
+        let db = surrealdb::Surreal::new::<surrealdb::engine::local::Mem>(())
+            .await
+            .unwrap();
+        db.query("RETURN 0; RETURN 1; RETURN 2;").await.unwrap()
+    }
+
+    #[tokio::test]
+    async fn test_statements_from_end_skip_taken_results() {
+        let mut response = three_statements().await;
+        let _: surrealdb::Value = response.take(0).unwrap();
+        let last: SurrealQR = response.take(RPath::Last).unwrap();
+        assert_eq!(last.0, Value::from(2));
+        let last: SurrealQR = response.take(RPath::Last).unwrap();
+        assert_eq!(last.0, Value::from(1));
+
+        let mut response = three_statements().await;
+        let _: surrealdb::Value = response.take(1).unwrap();
+        let first: SurrealQR = response.take(RPath::FromEnd(2)).unwrap();
+        assert_eq!(first.0, Value::from(0));
+        let last: SurrealQR = response.take(RPath::Last).unwrap();
+        assert_eq!(last.0, Value::from(2));
+        assert_eq!(
+            SurrealResponseError::from_surreal_error(
+                &response.take::<SurrealQR>(RPath::Last).unwrap_err()
+            ),
+            Some(SurrealResponseError::IndexOutOfRange { index: -1, len: 0 })
+        );
+    }
+}
@@ -0,0 +1,288 @@
+//! The string syntax of `RPath`, e.g. `users[0].address.city` or `items[-1]`.
+//!
+//! Keys are separated by `.` and indices are written in brackets, negative indices counting
+//! from the end. A `\` escapes the next character, so `version\.major` is a single key.
+//!
+//! Brackets also hold the segments that select several items: `[*]` for every item, `[1..5]`
+//! for a slice, and `[?status="paid"]` for the items whose value at a path equals a literal.
+//! A literal is a double quoted string, a number, `true`, `false` or `null`.
+use std::borrow::Cow;
+
+use surrealdb::sql::Value;
+
+use super::{RPath, SurrealResponseError};
+
+/// A parsed segment, whose key only owns its text when it contained escapes.
+#[derive(Debug, Clone, PartialEq)]
+pub(crate) enum Segment<'s> {
+    Field(Cow<'s, str>),
+    Index(i64),
+    All,
+    Slice(Option<usize>, Option<usize>),
+    Filter(&'s str, Value),
+}
+
+impl Segment<'_> {
+    pub(crate) fn as_path(&self) -> RPath<'_> {
+        match self {
+            Segment::Field(field) => RPath::Field(field),
+            Segment::Index(index) if *index < 0 => {
+                RPath::FromEnd(usize::try_from(index.unsigned_abs()).unwrap_or(usize::MAX))
+            }
+            Segment::Index(index) => RPath::Index(usize::try_from(*index).unwrap_or(usize::MAX)),
+            Segment::All => RPath::All,
+            Segment::Slice(start, end) => RPath::Slice {
+                start: *start,
+                end: *end,
+            },
+            Segment::Filter(field, equals) => RPath::Filter {
+                field,
+                equals: equals.clone(),
+            },
+        }
+    }
+}
+
+/// Parse `path` into its segments, an empty path having no segments.
+pub(crate) fn parse(path: &str) -> Result<Vec<Segment<'_>>, SurrealResponseError> {
+    let error = |message: String| SurrealResponseError::InvalidPath {
+        path: path.to_owned(),
+        message,
+    };
+
+    let mut segments = Vec::new();
+    let mut position = 0;
+    while position < path.len() {
+        let rest = &path[position..];
+        if let Some(rest) = rest.strip_prefix('[') {
+            let Some(end) = closing_bracket(rest) else {
+                return Err(error(format!("unclosed `[` at {}", position)));
+            };
+
+            let segment = parse_bracket(&rest[..end])
+                .map_err(|message| error(format!("{} at {}", message, position)))?;
+            segments.push(segment);
+            position += end + 2;
+            continue;
+        }
+
+        let start = if segments.is_empty() {
+            position
+        } else if rest.starts_with('.') {
+            position + 1
+        } else {
+            return Err(error(format!("expected `.` or `[` at {}", position)));
+        };
+
+        let (key, len) = parse_key(&path[start..])
+            .map_err(|(message, offset)| error(format!("{} at {}", message, start + offset)))?;
+        if key.is_empty() {
+            return Err(error(format!("empty key at {}", start)));
+        }
+
+        segments.push(Segment::Field(key));
+        position = start + len;
+    }
+
+    Ok(segments)
+}
+
+/// Find the `]` that closes a bracket, skipping the ones inside a quoted string.
+fn closing_bracket(input: &str) -> Option<usize> {
+    let mut in_string = false;
+    let mut escaped = false;
+    for (i, c) in input.char_indices() {
+        match c {
+            _ if escaped => escaped = false,
+            '\\' if in_string => escaped = true,
+            '"' => in_string = !in_string,
+            ']' if !in_string => return Some(i),
+            _ => {}
+        }
+    }
+
+    None
+}
+
+/// Parse the content of a bracket: an index, `*`, a slice or a filter.
+fn parse_bracket(content: &str) -> Result<Segment<'_>, String> {
+    if content == "*" {
+        return Ok(Segment::All);
+    }
+
+    if let Some(filter) = content.strip_prefix('?') {
+        let Some((field, literal)) = filter.split_once('=') else {
+            return Err(format!("expected `=` in the filter `{}`", filter));
+        };
+
+        if field.is_empty() || parse(field).is_err() {
+            return Err(format!("invalid filter field `{}`", field));
+        }
+
+        let Some(equals) = parse_literal(literal) else {
+            return Err(format!("invalid literal `{}`", literal));
+        };
+
+        return Ok(Segment::Filter(field, equals));
+    }
+
+    if let Some((start, end)) = content.split_once("..") {
+        let bound = |bound: &str| match bound {
+            "" => Ok(None),
+            bound => bound
+                .parse()
+                .map(Some)
+                .map_err(|_| format!("invalid slice `{}`", content)),
+        };
+
+        return Ok(Segment::Slice(bound(start)?, bound(end)?));
+    }
+
+    content
+        .parse()
+        .map(Segment::Index)
+        .map_err(|_| format!("invalid index `{}`", content))
+}
+
+fn parse_literal(literal: &str) -> Option<Value> {
+    match literal {
+        "true" => Some(Value::Bool(true)),
+        "false" => Some(Value::Bool(false)),
+        "null" => Some(Value::Null),
+        _ if literal.starts_with('"') => {
+            let inner = literal.strip_prefix('"')?.strip_suffix('"')?;
+            let mut string = String::with_capacity(inner.len());
+            let mut chars = inner.chars();
+            while let Some(c) = chars.next() {
+                match c {
+                    '\\' => string.push(chars.next()?),
+                    '"' => return None,
+                    c => string.push(c),
+                }
+            }
+
+            Some(Value::from(string))
+        }
+        _ => literal
+            .parse::<i64>()
+            .map(Value::from)
+            .ok()
+            .or_else(|| literal.parse::<f64>().ok().map(Value::from)),
+    }
+}
+
+/// Read a key up to the next unescaped `.` or `[`, returning it with the number of bytes read,
+/// or an error message with the offset it happened at.
+fn parse_key(input: &str) -> Result<(Cow<'_, str>, usize), (&'static str, usize)> {
+    let mut owned: Option<String> = None;
+    let mut chars = input.char_indices();
+    while let Some((i, c)) = chars.next() {
+        match c {
+            '.' | '[' => return Ok((finish_key(input, i, owned), i)),
+            ']' => return Err(("unexpected `]`", i)),
+            '\\' => {
+                let Some((_, escaped)) = chars.next() else {
+                    return Err(("dangling `\\`", i));
+                };
+
+                owned
+                    .get_or_insert_with(|| input[..i].to_owned())
+                    .push(escaped);
+            }
+            c => {
+                if let Some(owned) = owned.as_mut() {
+                    owned.push(c);
+                }
+            }
+        }
+    }
+
+    Ok((finish_key(input, input.len(), owned), input.len()))
+}
+
+fn finish_key(input: &str, end: usize, owned: Option<String>) -> Cow<'_, str> {
+    match owned {
+        Some(owned) => Cow::Owned(owned),
+        None => Cow::Borrowed(&input[..end]),
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+
+    fn field(key: &str) -> Segment<'_> {
+        Segment::Field(Cow::Borrowed(key))
+    }
+
+    #[test]
+    fn test_parse() {
+        assert_eq!(
+            parse("users[0].address.city"),
+            Ok(vec![
+                field("users"),
+                Segment::Index(0),
+                field("address"),
+                field("city")
+            ])
+        );
+        assert_eq!(
+            parse("[1][-1]"),
+            Ok(vec![Segment::Index(1), Segment::Index(-1)])
+        );
+        assert_eq!(
+            parse(r"version\.major.tags\[0\]"),
+            Ok(vec![field("version.major"), field("tags[0]")])
+        );
+        assert_eq!(parse(""), Ok(vec![]));
+    }
+
+    #[test]
+    fn test_parse_selections() {
+        assert_eq!(
+            parse("orders[*].total"),
+            Ok(vec![field("orders"), Segment::All, field("total")])
+        );
+        assert_eq!(
+            parse("[1..5][2..][..3]"),
+            Ok(vec![
+                Segment::Slice(Some(1), Some(5)),
+                Segment::Slice(Some(2), None),
+                Segment::Slice(None, Some(3)),
+            ])
+        );
+        assert_eq!(
+            parse(r#"[?customer.name="a]\"b"][?total=1.5][?paid=true]"#),
+            Ok(vec![
+                Segment::Filter("customer.name", Value::from("a]\"b")),
+                Segment::Filter("total", Value::from(1.5)),
+                Segment::Filter("paid", Value::Bool(true)),
+            ])
+        );
+    }
+
+    #[test]
+    fn test_parse_errors() {
+        for (path, message) in [
+            ("a..b", "empty key at 2"),
+            ("a.", "empty key at 2"),
+            ("a[0", "unclosed `[` at 1"),
+            ("a[x]", "invalid index `x` at 1"),
+            ("a[1..x]", "invalid slice `1..x` at 1"),
+            ("a[?b]", "expected `=` in the filter `b` at 1"),
+            ("a[?b..c=1]", "invalid filter field `b..c` at 1"),
+            ("a[?b=paid]", "invalid literal `paid` at 1"),
+            ("[0]a", "expected `.` or `[` at 3"),
+            ("a]", "unexpected `]` at 1"),
+            (r"a\", "dangling `\\` at 1"),
+        ] {
+            assert_eq!(
+                parse(path),
+                Err(SurrealResponseError::InvalidPath {
+                    path: path.to_owned(),
+                    message: message.to_owned(),
+                })
+            );
+        }
+    }
+}
@@ -0,0 +1,253 @@
+use surrealdb::sql::{
+    Array, Bytes, Datetime, Duration, Geometry, Number, Object, Strand, Thing, Uuid, Value,
+};
+
+use super::{array_of, lookup, object_of, RPath, Selection, SurrealQR, SurrealResponseError};
+use crate::proxy::default::SurrealDeserializer;
+
+static NONE: Value = Value::None;
+
+/// A borrowed view of a value, navigating it by reference.
+///
+/// Nothing is cloned until `deserialize` or `into_owned`, unlike `SurrealQR::get` which clones
+/// the value it returns.
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub struct SurrealQRRef<'a>(pub &'a Value);
+
+impl<'a> SurrealQRRef<'a> {
+    pub fn optional(self) -> Option<Self> {
+        match self.0 {
+            Value::None => None,
+            Value::Null => None,
+            _ => Some(self),
+        }
+    }
+
+    pub fn is_none(&self) -> bool {
+        self.0.is_none_or_null()
+    }
+
+    /// Borrow the value at `path`, a none value when it is missing.
+    ///
+    /// Paths that select several values are rejected, use `get_all` for them.
+    pub fn get<'p, T>(&self, path: T) -> Result<SurrealQRRef<'a>, SurrealResponseError>
+    where
+        T: Into<RPath<'p>>,
+    {
+        match lookup(self.0, &path.into())? {
+            Selection::One(value) => Ok(SurrealQRRef(value.unwrap_or(&NONE))),
+            Selection::Many(_) => Err(SurrealResponseError::AmbiguousPath),
+        }
+    }
+
+    /// Borrow every value selected by `path`, skipping the missing ones.
+    pub fn get_all<'p, T>(&self, path: T) -> Result<Vec<SurrealQRRef<'a>>, SurrealResponseError>
+    where
+        T: Into<RPath<'p>>,
+    {
+        let values = match lookup(self.0, &path.into())? {
+            Selection::One(value) => value.into_iter().collect(),
+            Selection::Many(values) => values,
+        };
+
+        Ok(values.into_iter().map(SurrealQRRef).collect())
+    }
+
+    pub fn object(&self) -> Result<Option<&'a Object>, SurrealResponseError> {
+        object_of(self.0)
+    }
+
+    pub fn array(&self) -> Result<Option<&'a Array>, SurrealResponseError> {
+        array_of(self.0)
+    }
+
+    pub fn deserialize<T>(&self) -> Result<T, SurrealResponseError>
+    where
+        T: SurrealDeserializer,
+    {
+        SurrealDeserializer::deserialize(self.0)
+    }
+
+    /// Deserialize the value at `path`, recording the path in the error.
+    ///
+    /// The values selected by a path through `[*]`, a slice or a filter are read as an array.
+    pub fn get_as<'p, T, P>(&self, path: P) -> Result<T, SurrealResponseError>
+    where
+        T: SurrealDeserializer,
+        P: Into<RPath<'p>>,
+    {
+        let path = path.into();
+        let result = match lookup(self.0, &path) {
+            Ok(Selection::One(value)) => T::deserialize(value.unwrap_or(&NONE)),
+            Ok(selection) => T::deserialize(&selection.into_value()),
+            Err(e) => Err(e),
+        };
+
+        result.map_err(|e| e.at_path(&path.error_path()))
+    }
+
+    /// Deserialize the value at `path`, `None` when it is missing or null.
+    pub fn get_opt<'p, T, P>(&self, path: P) -> Result<Option<T>, SurrealResponseError>
+    where
+        T: SurrealDeserializer,
+        P: Into<RPath<'p>>,
+    {
+        self.get_as::<Option<T>, P>(path)
+    }
+
+    /// Deserialize the value at `path`, the default of `T` when it is missing or null.
+    pub fn get_or_default<'p, T, P>(&self, path: P) -> Result<T, SurrealResponseError>
+    where
+        T: SurrealDeserializer + Default,
+        P: Into<RPath<'p>>,
+    {
+        Ok(self.get_opt(path)?.unwrap_or_default())
+    }
+
+    pub fn into_owned(self) -> SurrealQR {
+        SurrealQR(self.0.clone())
+    }
+
+    pub fn number(&self) -> Result<Option<&'a Number>, SurrealResponseError> {
+        match self.0 {
+            Value::None => Ok(None),
+            Value::Number(value) => Ok(Some(value)),
+            _ => Err(SurrealResponseError::ExpectedANumber),
+        }
+    }
+
+    pub fn strand(&self) -> Result<Option<&'a Strand>, SurrealResponseError> {
+        match self.0 {
+            Value::None => Ok(None),
+            Value::Strand(value) => Ok(Some(value)),
+            _ => Err(SurrealResponseError::ExpectedAStrand),
+        }
+    }
+
+    pub fn thing(&self) -> Result<Option<&'a Thing>, SurrealResponseError> {
+        match self.0 {
+            Value::None => Ok(None),
+            Value::Thing(value) => Ok(Some(value)),
+            _ => Err(SurrealResponseError::ExpectedAThing),
+        }
+    }
+
+    pub fn duration(&self) -> Result<Option<&'a Duration>, SurrealResponseError> {
+        match self.0 {
+            Value::None => Ok(None),
+            Value::Duration(value) => Ok(Some(value)),
+            _ => Err(SurrealResponseError::ExpectedADuration),
+        }
+    }
+
+    pub fn datetime(&self) -> Result<Option<&'a Datetime>, SurrealResponseError> {
+        match self.0 {
+            Value::None => Ok(None),
+            Value::Datetime(value) => Ok(Some(value)),
+            _ => Err(SurrealResponseError::ExpectedADatetime),
+        }
+    }
+
+    pub fn geometry(&self) -> Result<Option<&'a Geometry>, SurrealResponseError> {
+        match self.0 {
+            Value::None => Ok(None),
+            Value::Geometry(value) => Ok(Some(value)),
+            _ => Err(SurrealResponseError::ExpectedAGeometry),
+        }
+    }
+
+    pub fn boolean(&self) -> Result<Option<bool>, SurrealResponseError> {
+        match self.0 {
+            Value::None => Ok(None),
+            Value::Bool(value) => Ok(Some(*value)),
+            _ => Err(SurrealResponseError::ExpectedABool),
+        }
+    }
+
+    pub fn uuid(&self) -> Result<Option<&'a Uuid>, SurrealResponseError> {
+        match self.0 {
+            Value::None => Ok(None),
+            Value::Uuid(value) => Ok(Some(value)),
+            _ => Err(SurrealResponseError::ExpectedAUuid),
+        }
+    }
+
+    pub fn bytes(&self) -> Result<Option<&'a Bytes>, SurrealResponseError> {
+        match self.0 {
+            Value::None => Ok(None),
+            Value::Bytes(value) => Ok(Some(value)),
+            _ => Err(SurrealResponseError::ExpectedABytes),
+        }
+    }
+
+    pub fn as_i64(&self) -> Result<i64, SurrealResponseError> {
+        match self.number()? {
+            Some(Number::Int(value)) => Ok(*value),
+            _ => Err(SurrealResponseError::ExpectedANumberI64),
+        }
+    }
+
+    pub fn as_f64(&self) -> Result<f64, SurrealResponseError> {
+        match self.number()? {
+            Some(Number::Float(value)) => Ok(*value),
+            _ => Err(SurrealResponseError::ExpectedANumberF64),
+        }
+    }
+}
+
+impl<'a> From<&'a Value> for SurrealQRRef<'a> {
+    fn from(value: &'a Value) -> Self {
+        SurrealQRRef(value)
+    }
+}
+
+impl<'a> From<&'a SurrealQR> for SurrealQRRef<'a> {
+    fn from(value: &'a SurrealQR) -> Self {
+        SurrealQRRef(&value.0)
+    }
+}
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use std::collections::BTreeMap;
+
+    #[test]
+    fn test_borrowed_navigation() {
+        let qr = SurrealQR(Value::Object(Object::from(BTreeMap::from([(
+            "users".to_owned(),
+            Value::from(vec![
+                Value::Object(Object::from(BTreeMap::from([(
+                    "name".to_owned(),
+                    Value::from("john"),
+                )]))),
+                Value::Object(Object::from(BTreeMap::from([(
+                    "name".to_owned(),
+                    Value::from("jane"),
+                )]))),
+            ]),
+        )]))));
+
+        let view = qr.view();
+        let name = view.get("users[1].name").unwrap();
+        let Value::Object(root) = &qr.0 else {
+            unreachable!()
+        };
+        assert!(std::ptr::eq(view.get("users").unwrap().0, &root["users"]));
+        assert_eq!(name.strand().unwrap().map(|s| s.as_str()), Some("jane"));
+        assert_eq!(name.deserialize::<String>(), Ok("jane".to_owned()));
+        assert!(view.get("users[0].age").unwrap().is_none());
+        assert_eq!(
+            view.get("users[*].name").unwrap_err(),
+            SurrealResponseError::AmbiguousPath
+        );
+        assert_eq!(
+            view.get_all("users[*].name")
+                .unwrap()
+                .iter()
+                .map(|name| name.deserialize::<String>().unwrap())
+                .collect::<Vec<_>>(),
+            vec!["john".to_owned(), "jane".to_owned()]
+        );
+    }
+}
@@ -1,6 +1,12 @@
+//! Helpers rendering records as SurrealQL fragments.
+//!
+//! `record`, `content`, `array`, `val` and `relate` clone their target, since they only
+//! require `SurrealSerializer`, which the derived records implement. The `*_ref` variants
+//! serialize through `SurrealSerializerRef` instead and avoid the clone.
+
 use surrealdb::sql::{Data, Thing};
 
-use crate::proxy::default::SurrealSerializer;
+use crate::proxy::default::{SurrealSerializer, SurrealSerializerRef};
 use crate::surreal_edge::Edge;
 use crate::surreal_id::SurrealId;
 
@@ -11,8 +17,8 @@ where
     let id = target.id();
     format!(
         "{} {}",
-        id.to_string(),
-        Data::ContentExpression(target.clone().serialize()).to_string()
+        id,
+        Data::ContentExpression(target.clone().serialize())
     )
 }
 
@@ -35,7 +41,7 @@ where
     T: SurrealSerializer + Clone,
 {
     let array_value: Vec<surrealdb::sql::Value> =
-        target.into_iter().map(|v| v.clone().serialize()).collect();
+        target.iter().map(|v| v.clone().serialize()).collect();
 
     surrealdb::sql::Array::from(array_value).to_string()
 }
@@ -68,9 +74,168 @@ where
 
     format!(
         "RELATE {} -> {} -> {} {}",
-        in_id.to_string(),
+        in_id,
+        record_id,
+        out_id,
+        Data::ContentExpression(target.data.clone().serialize())
+    )
+}
+
+/// Same as `record`, serializing `target` through a reference instead of cloning it.
+pub fn record_ref<T>(target: &T) -> String
+where
+    T: SurrealSerializerRef + SurrealId,
+{
+    let id = target.id();
+    format!("{} {}", id, Data::ContentExpression(target.serialize_ref()))
+}
+
+/// Same as `content`, without cloning `target`.
+pub fn content_ref<T>(target: &T) -> String
+where
+    T: SurrealSerializerRef + ?Sized,
+{
+    Data::ContentExpression(target.serialize_ref()).to_string()
+}
+
+/// Same as `array`, without cloning the items.
+pub fn array_ref<T>(target: &[T]) -> String
+where
+    T: SurrealSerializerRef,
+{
+    let array_value: Vec<surrealdb::sql::Value> =
+        target.iter().map(|v| v.serialize_ref()).collect();
+
+    surrealdb::sql::Array::from(array_value).to_string()
+}
+
+/// Same as `val`, without cloning `target`.
+pub fn val_ref<T>(target: &T) -> String
+where
+    T: SurrealSerializerRef + ?Sized,
+{
+    target.serialize_ref().to_string()
+}
+
+/// Same as `relate`, without cloning the edge data.
+pub fn relate_ref<I, R, O>(target: &Edge<I, R, O>) -> String
+where
+    R: SurrealSerializer + SurrealSerializerRef + SurrealId,
+    I: SurrealId,
+    O: SurrealId,
+{
+    let record_id: Thing = target.data.id();
+    let in_id: Thing = target
+        .r#in
+        .as_ref()
+        .expect("In direction cannot be null when serialize")
+        .id();
+    let out_id: Thing = target
+        .out
+        .as_ref()
+        .expect("Out direction cannot be null when serialize")
+        .id();
+
+    format!(
+        "RELATE {} -> {} -> {} {}",
+        in_id,
         record_id,
-        out_id.to_string(),
-        Data::ContentExpression(target.data.clone().serialize()).to_string()
+        out_id,
+        Data::ContentExpression(target.data.serialize_ref())
     )
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use crate::surreal_edge::IntoRelation;
+    use crate::surreal_id::Link;
+    use std::collections::BTreeMap;
+    use surrealdb::sql::{Object, Value};
+
+    #[derive(Debug, Clone)]
+    struct User {
+        id: Thing,
+        name: String,
+    }
+
+    impl SurrealId for User {
+        fn id(&self) -> Thing {
+            self.id.clone()
+        }
+    }
+
+    impl SurrealSerializer for User {
+        fn serialize(self) -> Value {
+            self.serialize_ref()
+        }
+    }
+
+    impl SurrealSerializerRef for User {
+        fn serialize_ref(&self) -> Value {
+            Value::Object(Object::from(BTreeMap::from([
+                ("id".to_owned(), Value::from(self.id.clone())),
+                ("name".to_owned(), Value::from(self.name.clone())),
+            ])))
+        }
+    }
+
+    fn user(name: &str) -> User {
+        User {
+            id: Thing::from(("user", name)),
+            name: name.to_owned(),
+        }
+    }
+
+    #[test]
+    fn test_ref_helpers_match_cloning_helpers() {
+        let john = user("john");
+        let users = [user("john"), user("jane")];
+
+        assert_eq!(
+            record_ref(&john),
+            "user:john CONTENT { id: user:john, name: 'john' }"
+        );
+        assert_eq!(record_ref(&john), record(&john));
+        assert_eq!(content_ref(&john), content(&john));
+        assert_eq!(array_ref(&users), array(&users));
+        assert_eq!(val_ref(&john), val(&john));
+        assert_eq!(val_ref("text"), "'text'");
+        assert_eq!(array_ref(&[1i64, 2]), "[1, 2]");
+    }
+
+    #[test]
+    fn test_relate_ref() {
+        let follows = User {
+            id: Thing::from(("follows", "john_jane")),
+            name: "follows".to_owned(),
+        };
+        let edge = follows.relate(user("john"), user("jane"));
+
+        assert_eq!(
+            relate_ref(&edge),
+            "RELATE user:john -> follows:john_jane -> user:jane CONTENT { id: follows:john_jane, name: 'follows' }"
+        );
+        assert_eq!(relate_ref(&edge), relate(&edge));
+    }
+
+    #[test]
+    fn test_link_and_edge_serialize_to_their_id() {
+        let john = user("john");
+        let link = Link::Record(john.clone());
+        let edge = user("follows").relate(john.clone(), user("jane"));
+
+        assert_eq!(link.serialize_ref(), Value::from(john.id()));
+        assert_eq!(link.clone().serialize(), link.serialize_ref());
+        assert_eq!(
+            Link::<User>::Id(john.id()).serialize_ref(),
+            Value::from(john.id())
+        );
+        assert_eq!(
+            edge.serialize_ref(),
+            Value::from(Thing::from(("user", "follows")))
+        );
+        assert_eq!(edge.clone().serialize(), edge.serialize_ref());
+        assert_eq!(val_ref(&edge), "user:follows");
+    }
+}